@@ -0,0 +1,81 @@
+//! Throughput of word and name generation, comparing the allocating APIs with
+//! the ones that write into a reused buffer.
+//!
+//! ```text
+//! cargo bench --bench generation
+//! ```
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use phyla_lang::{CacheConfig, CulturalProfile, Geography, Language, CORE_VOCABULARY};
+use std::hint::black_box;
+
+/// Names generated per iteration.
+const NAMES: u64 = 1000;
+
+fn language() -> Language {
+    let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    let mut language = Language::from_culture(culture, Geography::Coastal, 12345);
+    // Measure generation itself, not cache lookups
+    language.set_cache_config(CacheConfig::disabled());
+    language
+}
+
+fn names(c: &mut Criterion) {
+    let language = language();
+    let mut group = c.benchmark_group("simple_names");
+    group.throughput(Throughput::Elements(NAMES));
+
+    group.bench_function("generate_simple_name", |b| {
+        b.iter(|| {
+            for id in 0..NAMES {
+                black_box(language.naming.generate_simple_name(black_box(id)));
+            }
+        })
+    });
+    group.bench_function("generate_simple_name_into", |b| {
+        let mut name = String::new();
+        b.iter(|| {
+            for id in 0..NAMES {
+                name.clear();
+                language
+                    .naming
+                    .generate_simple_name_into(black_box(id), &mut name)
+                    .unwrap();
+                black_box(&name);
+            }
+        })
+    });
+    group.finish();
+}
+
+fn words(c: &mut Criterion) {
+    let language = language();
+    let mut group = c.benchmark_group("words");
+    group.throughput(Throughput::Elements(CORE_VOCABULARY.len() as u64));
+
+    group.bench_function("translate_word", |b| {
+        b.iter(|| {
+            for concept in CORE_VOCABULARY {
+                black_box(language.translate_word(black_box(concept)));
+            }
+        })
+    });
+    group.bench_function("translate_word_into", |b| {
+        b.iter_batched_ref(
+            String::new,
+            |word| {
+                for concept in CORE_VOCABULARY {
+                    word.clear();
+                    language
+                        .translate_word_into(black_box(concept), word)
+                        .unwrap();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, names, words);
+criterion_main!(benches);
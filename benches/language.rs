@@ -0,0 +1,93 @@
+//! The costs behind the README's performance figures: building a language,
+//! translating words with and without the cache, and each name generator.
+//!
+//! ```text
+//! cargo bench --bench language
+//! ```
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use phyla_lang::{
+    Characteristic, CulturalProfile, EpithetContext, Geography, Language, LinguisticGenome,
+    MorphemeDatabase, PersonalNameContext, PlaceNameContext, PlaceType,
+};
+use std::hint::black_box;
+
+const SEED: u64 = 12345;
+
+fn culture() -> CulturalProfile {
+    CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0)
+}
+
+fn language() -> Language {
+    Language::from_culture(culture(), Geography::Coastal, SEED)
+}
+
+fn construction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("construction");
+    let culture = culture();
+    let genome = LinguisticGenome::from_culture(culture, Geography::Coastal, SEED);
+
+    group.bench_function("genome", |b| {
+        b.iter(|| {
+            LinguisticGenome::from_culture(black_box(culture), Geography::Coastal, black_box(SEED))
+        })
+    });
+    group.bench_function("morpheme_database", |b| {
+        b.iter(|| MorphemeDatabase::from_genome(black_box(&genome), &culture, &Geography::Coastal))
+    });
+    group.bench_function("language", |b| {
+        b.iter(|| Language::from_culture(black_box(culture), Geography::Coastal, black_box(SEED)))
+    });
+    group.finish();
+}
+
+fn translation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("translate_word");
+    let language = language();
+
+    group.bench_function("cold", |b| {
+        b.iter_batched(
+            || language.clear_cache(),
+            |()| language.translate_word(black_box("mountain")),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("warm", |b| {
+        language.translate_word("mountain");
+        b.iter(|| language.translate_word(black_box("mountain")))
+    });
+    group.finish();
+}
+
+fn names(c: &mut Criterion) {
+    let mut group = c.benchmark_group("names");
+    let language = language();
+    let naming = &language.naming;
+
+    group.bench_function("simple", |b| {
+        b.iter(|| naming.generate_simple_name(black_box(42)))
+    });
+    group.bench_function("personal", |b| {
+        let context = PersonalNameContext::simple(42);
+        b.iter(|| naming.generate_personal_name(black_box(&context)))
+    });
+    group.bench_function("personal_with_parent", |b| {
+        let context = PersonalNameContext::with_parent(42, "Aran".to_string());
+        b.iter(|| naming.generate_personal_name(black_box(&context)))
+    });
+    group.bench_function("compound", |b| {
+        b.iter(|| naming.generate_compound_name(black_box(42), 2))
+    });
+    group.bench_function("place", |b| {
+        let context = PlaceNameContext::new(42, PlaceType::Settlement);
+        b.iter(|| naming.generate_place_name(black_box(&context)))
+    });
+    group.bench_function("epithet", |b| {
+        let context = EpithetContext::new(42).with_characteristic(Characteristic::Wise);
+        b.iter(|| naming.generate_epithet(black_box(&context)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, construction, translation, names);
+criterion_main!(benches);
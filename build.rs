@@ -0,0 +1,30 @@
+//! Generates the C header `phyla.h` in `OUT_DIR` when the `ffi` feature is
+//! enabled.
+//!
+//! The copy in `include/` is checked in, and a test fails if it differs from
+//! the generated one.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "ffi")]
+    if let Err(error) = generate_header() {
+        println!("cargo:warning=could not generate the C header: {}", error);
+    }
+}
+
+#[cfg(feature = "ffi")]
+fn generate_header() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let out_dir = std::env::var_os("OUT_DIR").ok_or("OUT_DIR is not set")?;
+    let config = cbindgen::Config::from_file("cbindgen.toml")?;
+    // Only the C API goes in the header, not the rest of the library
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src("src/ffi.rs")
+        .generate()?
+        .write_to_file(std::path::Path::new(&out_dir).join("phyla.h"));
+    Ok(())
+}
@@ -0,0 +1,49 @@
+/*
+ * Generate words and names through the C API.
+ *
+ *     cargo rustc --lib --release --features ffi --crate-type cdylib
+ *     cc examples/c/names.c -Iinclude -Ltarget/release -lphyla_lang -o names
+ *     LD_LIBRARY_PATH=target/release ./names
+ */
+
+#include <stdio.h>
+
+#include "phyla.h"
+
+static int check(PhylaStatus status) {
+    if (status != PHYLA_STATUS_OK) {
+        fprintf(stderr, "phyla: %s\n", phyla_status_message(status));
+        return 0;
+    }
+    return 1;
+}
+
+int main(void) {
+    PhylaCulture coastal = {4.0f, 3.0f, 2.0f, 3.0f, 3.0f, 4.0f};
+    PhylaLanguage *language = NULL;
+    if (!check(phyla_language_new(&coastal, PHYLA_GEOGRAPHY_COASTAL, 12345, &language))) {
+        return 1;
+    }
+
+    char *word = NULL;
+    if (check(phyla_translate_word(language, "water", &word))) {
+        printf("water: %s\n", word);
+        phyla_string_free(word);
+    }
+
+    /* The naming system outlives the language it came from */
+    PhylaNamingSystem *naming = NULL;
+    check(phyla_language_naming(language, &naming));
+    phyla_language_free(language);
+
+    for (uint64_t id = 0; id < 3; id++) {
+        char *name = NULL;
+        if (check(phyla_naming_place_name(naming, id, PHYLA_PLACE_TYPE_SETTLEMENT, &name))) {
+            printf("settlement %llu: %s\n", (unsigned long long)id, name);
+            phyla_string_free(name);
+        }
+    }
+
+    phyla_naming_free(naming);
+    return 0;
+}
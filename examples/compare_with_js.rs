@@ -6,7 +6,7 @@
 //! - River Valley Scholars
 //! - Desert Nomads
 
-use phyla_lang::{CulturalProfile, Geography, Language};
+use phyla_lang::{AlgorithmVersion, CulturalProfile, Geography, Language};
 
 fn main() {
     println!("=== Comparing Rust Implementation with JavaScript Demo ===\n");
@@ -87,7 +87,7 @@ fn create_melodic_coastal() -> Language {
         4.0, // emotionality (high for emotional culture)
     );
 
-    Language::from_culture(culture, Geography::Coastal, 1001)
+    js_compatible(culture, Geography::Coastal, 1001)
 }
 
 fn create_mountain_warriors() -> Language {
@@ -101,7 +101,7 @@ fn create_mountain_warriors() -> Language {
         2.0, // emotionality (stoic)
     );
 
-    Language::from_culture(culture, Geography::Mountains, 1002)
+    js_compatible(culture, Geography::Mountains, 1002)
 }
 
 fn create_river_scholars() -> Language {
@@ -115,7 +115,7 @@ fn create_river_scholars() -> Language {
         3.0, // emotionality
     );
 
-    Language::from_culture(culture, Geography::RiverValley, 1003)
+    js_compatible(culture, Geography::RiverValley, 1003)
 }
 
 fn create_desert_nomads() -> Language {
@@ -129,7 +129,12 @@ fn create_desert_nomads() -> Language {
         2.0, // emotionality (hardy, resilient)
     );
 
-    Language::from_culture(culture, Geography::Desert, 1004)
+    js_compatible(culture, Geography::Desert, 1004)
+}
+
+/// Create a language with the algorithms of the JavaScript implementation.
+fn js_compatible(culture: CulturalProfile, geography: Geography, seed: u64) -> Language {
+    Language::from_culture_with_version(culture, geography, seed, AlgorithmVersion::V1)
 }
 
 fn print_phonology(language: &Language) {
@@ -0,0 +1,242 @@
+/* phyla-lang C API. Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef PHYLA_H
+#define PHYLA_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The result of a call.
+typedef enum PhylaStatus {
+  // The call succeeded
+  PHYLA_STATUS_OK = 0,
+  // A required pointer was null
+  PHYLA_STATUS_NULL_POINTER = 1,
+  // A string was not valid UTF-8, or a result contained a NUL byte
+  PHYLA_STATUS_INVALID_STRING = 2,
+  // A trait score, geography, place type or characteristic was out of range
+  PHYLA_STATUS_INVALID_ARGUMENT = 3,
+  // A language file could not be read or written
+  PHYLA_STATUS_INVALID_JSON = 4,
+  // The library panicked; the handles passed in should not be used again
+  PHYLA_STATUS_PANIC = 5,
+} PhylaStatus;
+
+// Geographies, as passed to `phyla_language_new`.
+typedef enum PhylaGeography {
+  PHYLA_GEOGRAPHY_MOUNTAINS = 0,
+  PHYLA_GEOGRAPHY_COASTAL = 1,
+  PHYLA_GEOGRAPHY_DESERT = 2,
+  PHYLA_GEOGRAPHY_FOREST = 3,
+  PHYLA_GEOGRAPHY_PLAINS = 4,
+  PHYLA_GEOGRAPHY_RIVER_VALLEY = 5,
+} PhylaGeography;
+
+// Kinds of place, as passed to `phyla_naming_place_name`.
+typedef enum PhylaPlaceType {
+  PHYLA_PLACE_TYPE_SETTLEMENT = 0,
+  PHYLA_PLACE_TYPE_NATURAL = 1,
+  PHYLA_PLACE_TYPE_LANDMARK = 2,
+  PHYLA_PLACE_TYPE_REGION = 3,
+} PhylaPlaceType;
+
+// Characteristics, as passed to `phyla_naming_epithet`.
+typedef enum PhylaCharacteristic {
+  PHYLA_CHARACTERISTIC_TALL = 0,
+  PHYLA_CHARACTERISTIC_SHORT = 1,
+  PHYLA_CHARACTERISTIC_STRONG = 2,
+  PHYLA_CHARACTERISTIC_SWIFT = 3,
+  PHYLA_CHARACTERISTIC_WISE = 4,
+  PHYLA_CHARACTERISTIC_CUNNING = 5,
+  PHYLA_CHARACTERISTIC_MAD = 6,
+  PHYLA_CHARACTERISTIC_HONEST = 7,
+  PHYLA_CHARACTERISTIC_BRAVE = 8,
+  PHYLA_CHARACTERISTIC_CRUEL = 9,
+  PHYLA_CHARACTERISTIC_JUST = 10,
+  PHYLA_CHARACTERISTIC_SILENT = 11,
+  PHYLA_CHARACTERISTIC_LOUD = 12,
+  PHYLA_CHARACTERISTIC_BELOVED = 13,
+  PHYLA_CHARACTERISTIC_FEARED = 14,
+} PhylaCharacteristic;
+
+// An opaque handle to a language.
+typedef struct PhylaLanguage PhylaLanguage;
+
+// An opaque handle to a naming system.
+typedef struct PhylaNamingSystem PhylaNamingSystem;
+
+// Personality trait scores, each from 1 to 5.
+typedef struct PhylaCulture {
+  float agreeableness;
+  float openness;
+  float conscientiousness;
+  float extraversion;
+  float honesty_humility;
+  float emotionality;
+} PhylaCulture;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Describe a status in English.
+//
+// Takes the status as an integer so that any value from C is safe to pass. The
+// returned string is static and must not be freed.
+const char *phyla_status_message(uint32_t status);
+
+// Create a language from a culture, a geography and a seed.
+//
+// # Safety
+//
+// `culture` must point to a valid `PhylaCulture` and `out` to writable memory.
+// The language written to `out` must be freed with `phyla_language_free`.
+enum PhylaStatus phyla_language_new(const struct PhylaCulture *culture,
+                                    uint32_t geography,
+                                    uint64_t seed,
+                                    struct PhylaLanguage **out);
+
+// Load a language saved with `phyla_language_to_json` or `Language::to_json`.
+//
+// # Safety
+//
+// `json` must be a NUL-terminated string and `out` must point to writable
+// memory. The language written to `out` must be freed with `phyla_language_free`.
+enum PhylaStatus phyla_language_from_json(const char *json, struct PhylaLanguage **out);
+
+// Save a language in the versioned language file format.
+//
+// # Safety
+//
+// `language` must be a live handle and `out` must point to writable memory.
+// The string written to `out` must be freed with `phyla_string_free`.
+enum PhylaStatus phyla_language_to_json(const struct PhylaLanguage *language, char **out);
+
+// Free a language. Passing null does nothing.
+//
+// # Safety
+//
+// `language` must be null or a handle from this library that has not been freed.
+void phyla_language_free(struct PhylaLanguage *language);
+
+// Write the language's ID.
+//
+// # Safety
+//
+// `language` must be a live handle and `out` must point to writable memory.
+// The string written to `out` must be freed with `phyla_string_free`.
+enum PhylaStatus phyla_language_id(const struct PhylaLanguage *language, char **out);
+
+// Translate a concept to a word.
+//
+// # Safety
+//
+// `language` must be a live handle, `concept` a NUL-terminated string and `out`
+// writable memory. The string written to `out` must be freed with
+// `phyla_string_free`.
+enum PhylaStatus phyla_translate_word(const struct PhylaLanguage *language,
+                                      const char *concept,
+                                      char **out);
+
+// Translate a phrase, applying the language's word order.
+//
+// # Safety
+//
+// `language` must be a live handle, `phrase` a NUL-terminated string and `out`
+// writable memory. The string written to `out` must be freed with
+// `phyla_string_free`.
+enum PhylaStatus phyla_translate_phrase(const struct PhylaLanguage *language,
+                                        const char *phrase,
+                                        char **out);
+
+// Copy a language's naming system into a handle of its own.
+//
+// The naming system does not borrow the language, which may be freed first.
+//
+// # Safety
+//
+// `language` must be a live handle and `out` must point to writable memory.
+// The naming system written to `out` must be freed with `phyla_naming_free`.
+enum PhylaStatus phyla_language_naming(const struct PhylaLanguage *language,
+                                       struct PhylaNamingSystem **out);
+
+// Free a naming system. Passing null does nothing.
+//
+// # Safety
+//
+// `naming` must be null or a handle from this library that has not been freed.
+void phyla_naming_free(struct PhylaNamingSystem *naming);
+
+// Generate a name from the language's sounds alone.
+//
+// # Safety
+//
+// `naming` must be a live handle and `out` must point to writable memory.
+// The string written to `out` must be freed with `phyla_string_free`.
+enum PhylaStatus phyla_naming_simple_name(const struct PhylaNamingSystem *naming,
+                                          uint64_t id,
+                                          char **out);
+
+// Generate a personal name. `parent` may be null; in patronymic cultures a
+// parent's name becomes part of the name.
+//
+// # Safety
+//
+// `naming` must be a live handle, `parent` null or a NUL-terminated string, and
+// `out` writable memory. The string written to `out` must be freed with
+// `phyla_string_free`.
+enum PhylaStatus phyla_naming_personal_name(const struct PhylaNamingSystem *naming,
+                                            uint64_t id,
+                                            const char *parent,
+                                            char **out);
+
+// Generate a name compounded from `count` meaningful morphemes.
+//
+// # Safety
+//
+// `naming` must be a live handle and `out` must point to writable memory.
+// The string written to `out` must be freed with `phyla_string_free`.
+enum PhylaStatus phyla_naming_compound_name(const struct PhylaNamingSystem *naming,
+                                            uint64_t id,
+                                            size_t count,
+                                            char **out);
+
+// Generate a place name for a `PhylaPlaceType`.
+//
+// # Safety
+//
+// `naming` must be a live handle and `out` must point to writable memory.
+// The string written to `out` must be freed with `phyla_string_free`.
+enum PhylaStatus phyla_naming_place_name(const struct PhylaNamingSystem *naming,
+                                         uint64_t id,
+                                         uint32_t place_type,
+                                         char **out);
+
+// Generate an epithet for a `PhylaCharacteristic`.
+//
+// Writes null to `out` when the culture does not use epithets.
+//
+// # Safety
+//
+// `naming` must be a live handle and `out` must point to writable memory.
+// A string written to `out` must be freed with `phyla_string_free`.
+enum PhylaStatus phyla_naming_epithet(const struct PhylaNamingSystem *naming,
+                                      uint64_t id,
+                                      uint32_t characteristic,
+                                      char **out);
+
+// Free a string returned by this library. Passing null does nothing.
+//
+// # Safety
+//
+// `string` must be null or a string from this library that has not been freed.
+void phyla_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PHYLA_H */
@@ -0,0 +1,201 @@
+//! Batch generation: many words or names in one call.
+//!
+//! With the `rayon` feature, batches are generated on all cores. Either way a
+//! batch holds exactly what the one-at-a-time methods return, in input order.
+
+use crate::language::Language;
+use crate::naming::personal::PersonalNameContext;
+use crate::naming::place::{PlaceNameContext, PlaceType};
+use crate::naming::NamingSystem;
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Map each item to a string, keeping the items' order.
+#[cfg(feature = "rayon")]
+fn map_slice<T, F>(items: &[T], f: F) -> Vec<String>
+where
+    T: Sync,
+    F: Fn(&T) -> String + Sync + Send,
+{
+    items.par_iter().map(f).collect()
+}
+
+/// Map each item to a string, keeping the items' order.
+#[cfg(not(feature = "rayon"))]
+fn map_slice<T, F>(items: &[T], f: F) -> Vec<String>
+where
+    F: Fn(&T) -> String,
+{
+    items.iter().map(f).collect()
+}
+
+/// Map each ID to a string, keeping the IDs' order.
+#[cfg(feature = "rayon")]
+fn map_range<F>(ids: Range<u64>, f: F) -> Vec<String>
+where
+    F: Fn(u64) -> String + Sync + Send,
+{
+    ids.into_par_iter().map(f).collect()
+}
+
+/// Map each ID to a string, keeping the IDs' order.
+#[cfg(not(feature = "rayon"))]
+fn map_range<F>(ids: Range<u64>, f: F) -> Vec<String>
+where
+    F: Fn(u64) -> String,
+{
+    ids.map(f).collect()
+}
+
+impl Language {
+    /// Translate many concepts, returning the words in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{CulturalProfile, Geography, Language, CORE_VOCABULARY};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    /// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+    ///
+    /// let words = language.translate_words(CORE_VOCABULARY.iter().copied());
+    /// assert_eq!(words[0], language.translate_word(CORE_VOCABULARY[0]));
+    /// ```
+    pub fn translate_words<I>(&self, concepts: I) -> Vec<String>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let concepts: Vec<I::Item> = concepts.into_iter().collect();
+        let concepts: Vec<&str> = concepts.iter().map(AsRef::as_ref).collect();
+        map_slice(&concepts, |concept| self.translate_word(concept))
+    }
+
+    /// Translate many phrases, returning the translations in the same order.
+    pub fn translate_phrases<I>(&self, phrases: I) -> Vec<String>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let phrases: Vec<I::Item> = phrases.into_iter().collect();
+        let phrases: Vec<&str> = phrases.iter().map(AsRef::as_ref).collect();
+        map_slice(&phrases, |phrase| self.translate_phrase(phrase))
+    }
+}
+
+impl NamingSystem {
+    /// Generate simple names for a range of IDs.
+    pub fn generate_simple_names(&self, ids: Range<u64>) -> Vec<String> {
+        map_range(ids, |id| self.generate_simple_name(id))
+    }
+
+    /// Generate personal names for a range of entity IDs, with no parents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{CulturalProfile, Geography, Language, PersonalNameContext};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    /// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+    ///
+    /// let names = language.naming.generate_personal_names(1000..2000);
+    /// let context = PersonalNameContext::simple(1005);
+    /// assert_eq!(names[5], language.naming.generate_personal_name(&context));
+    /// ```
+    pub fn generate_personal_names(&self, ids: Range<u64>) -> Vec<String> {
+        map_range(ids, |id| {
+            self.generate_personal_name(&PersonalNameContext::simple(id))
+        })
+    }
+
+    /// Generate a personal name for each context.
+    pub fn generate_personal_names_for(&self, contexts: &[PersonalNameContext]) -> Vec<String> {
+        map_slice(contexts, |context| self.generate_personal_name(context))
+    }
+
+    /// Generate place names of one type for a range of place IDs.
+    pub fn generate_place_names(&self, ids: Range<u64>, place_type: PlaceType) -> Vec<String> {
+        map_range(ids, |id| {
+            self.generate_place_name(&PlaceNameContext::new(id, place_type))
+        })
+    }
+
+    /// Generate a place name for each context.
+    pub fn generate_place_names_for(&self, contexts: &[PlaceNameContext]) -> Vec<String> {
+        map_slice(contexts, |context| self.generate_place_name(context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::{CulturalProfile, Geography};
+    use crate::lexicon::CORE_VOCABULARY;
+
+    fn language() -> Language {
+        // Patronymic, so parents change the names
+        let culture = CulturalProfile::new(3.0, 3.0, 4.5, 3.0, 3.0, 3.0);
+        Language::from_culture(culture, Geography::Mountains, 12345)
+    }
+
+    #[test]
+    fn test_batches_match_single_calls() {
+        let language = language();
+        let naming = &language.naming;
+
+        let words = language.translate_words(CORE_VOCABULARY);
+        let expected: Vec<String> = CORE_VOCABULARY
+            .iter()
+            .map(|c| language.translate_word(c))
+            .collect();
+        assert_eq!(words, expected);
+
+        let simple: Vec<String> = (500..700)
+            .map(|id| naming.generate_simple_name(id))
+            .collect();
+        assert_eq!(naming.generate_simple_names(500..700), simple);
+
+        let places: Vec<String> = (0..200)
+            .map(|id| naming.generate_place_name(&PlaceNameContext::new(id, PlaceType::Natural)))
+            .collect();
+        assert_eq!(
+            naming.generate_place_names(0..200, PlaceType::Natural),
+            places
+        );
+    }
+
+    #[test]
+    fn test_contexts_keep_their_order() {
+        let naming = language().naming;
+        let contexts: Vec<PersonalNameContext> = (0..300)
+            .map(|id| PersonalNameContext::with_parent(id, format!("Parent{}", id % 7)))
+            .collect();
+
+        let names = naming.generate_personal_names_for(&contexts);
+        for (context, name) in contexts.iter().zip(&names) {
+            assert_eq!(name, &naming.generate_personal_name(context));
+        }
+    }
+
+    #[test]
+    fn test_owned_and_empty_inputs() {
+        let language = language();
+        let owned = vec!["water".to_string(), "Fire".to_string()];
+        assert_eq!(
+            language.translate_words(&owned),
+            [
+                language.translate_word("water"),
+                language.translate_word("fire")
+            ]
+        );
+        assert_eq!(
+            language.translate_phrases(["the big river"]),
+            [language.translate_phrase("the big river")]
+        );
+        assert!(language.naming.generate_simple_names(5..5).is_empty());
+    }
+}
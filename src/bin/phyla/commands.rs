@@ -0,0 +1,332 @@
+//! The subcommands of `phyla`, and the reports they print.
+
+use crate::OutputFormat;
+use clap::ValueEnum;
+use phyla_lang::{
+    Characteristic, CombiningRule, CulturalProfile, EpithetContext, Etymology, Geography, Language,
+    MorphologyType, NamePattern, PersonalNameContext, PlaceNameContext, PlaceType, StressPattern,
+    WordOrder, CORE_VOCABULARY,
+};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// Characteristics epithets cycle through.
+const CHARACTERISTICS: &[Characteristic] = &[
+    Characteristic::Wise,
+    Characteristic::Brave,
+    Characteristic::Strong,
+    Characteristic::Swift,
+    Characteristic::Just,
+    Characteristic::Cunning,
+    Characteristic::Beloved,
+    Characteristic::Feared,
+];
+
+/// The kinds of names `phyla names` generates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum NameKind {
+    /// Personal names, following the culture's naming pattern
+    Personal,
+    /// Names compounded from meaningful morphemes
+    Compound,
+    /// Villages, towns and cities
+    Settlement,
+    /// Mountains, rivers and forests
+    Natural,
+    /// Bridges, towers and fortresses
+    Landmark,
+    /// Valleys, plains and territories
+    Region,
+    /// Personal names with an epithet, where the culture uses them
+    Epithet,
+}
+
+/// A translated word or phrase.
+#[derive(Debug, Serialize)]
+pub struct Translation {
+    pub input: String,
+    pub translation: String,
+}
+
+/// A dictionary entry.
+#[derive(Debug, Serialize)]
+pub struct DictEntry {
+    pub concept: String,
+    pub word: String,
+    pub etymology: Etymology,
+}
+
+/// A summary of a language's sounds and grammar.
+#[derive(Debug, Serialize)]
+pub struct Info {
+    pub id: String,
+    pub algorithm_version: u32,
+    pub geography: Geography,
+    pub culture: CulturalProfile,
+    pub stops: Vec<String>,
+    pub fricatives: Vec<String>,
+    pub nasals: Vec<String>,
+    pub liquids: Vec<String>,
+    pub glides: Vec<String>,
+    pub vowels: Vec<String>,
+    pub loan_phonemes: Vec<String>,
+    pub syllable_patterns: Vec<&'static str>,
+    pub word_order: WordOrder,
+    pub morphology: MorphologyType,
+    pub stress: StressPattern,
+    pub name_pattern: NamePattern,
+    pub combining_rule: CombiningRule,
+}
+
+/// Load a language file.
+pub fn load(path: &Path) -> Result<Language, Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Language::load(BufReader::new(file)).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+/// Save a language file, syncing it to disk before reporting success.
+pub fn save(language: &Language, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut writer = BufWriter::new(file);
+    language.save(&mut writer)?;
+    // Don't leave flushing to drop, which ignores errors
+    let file = writer.into_inner().map_err(|e| e.into_error())?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Print a report as JSON or as text.
+pub fn print<T: Serialize>(format: OutputFormat, report: &T, text: impl FnOnce(&T) -> String) {
+    match format {
+        OutputFormat::Json => {
+            // Reports are plain data, which always serializes
+            println!("{}", serde_json::to_string_pretty(report).unwrap());
+        }
+        OutputFormat::Text => println!("{}", text(report)),
+    }
+}
+
+/// Print a list of strings, one per line as text.
+pub fn print_list(format: OutputFormat, items: &[String]) {
+    print(format, &items, |items| items.join("\n"));
+}
+
+/// `phyla new`: create a language and save it.
+pub fn new(
+    format: OutputFormat,
+    path: &Path,
+    culture: CulturalProfile,
+    geography: Geography,
+    seed: u64,
+    id: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut language = Language::try_from_culture(culture, geography, seed)?;
+    if let Some(id) = id {
+        language.id = id;
+    }
+    save(&language, path)?;
+
+    let report = serde_json::json!({ "id": language.id, "path": path });
+    print(format, &report, |_| {
+        format!("Created {} in {}", language.id, path.display())
+    });
+    Ok(())
+}
+
+/// `phyla translate`: translate each input as a word, or as a phrase if it has spaces.
+pub fn translate(format: OutputFormat, language: &Language, inputs: &[String]) {
+    let translations: Vec<Translation> = inputs
+        .iter()
+        .map(|input| Translation {
+            input: input.clone(),
+            translation: translate_text(language, input),
+        })
+        .collect();
+
+    print(format, &translations, |translations| {
+        let width = translations
+            .iter()
+            .map(|t| t.input.len())
+            .max()
+            .unwrap_or(0);
+        translations
+            .iter()
+            .map(|t| format!("{:width$}  {}", t.input, t.translation, width = width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+}
+
+/// Translate a word, or a phrase if the text has spaces.
+pub fn translate_text(language: &Language, text: &str) -> String {
+    if text.split_whitespace().nth(1).is_some() {
+        language.translate_phrase(text)
+    } else {
+        language.translate_word(text.trim())
+    }
+}
+
+/// `phyla names`: generate names numbered from `start`.
+pub fn names(
+    language: &Language,
+    kind: NameKind,
+    start: u64,
+    count: u64,
+    parent: Option<&str>,
+) -> Vec<String> {
+    let naming = &language.naming;
+    let place = |id, place_type| naming.generate_place_name(&PlaceNameContext::new(id, place_type));
+    let personal = |id| match parent {
+        Some(parent) => {
+            naming.generate_personal_name(&PersonalNameContext::with_parent(id, parent.to_string()))
+        }
+        None => naming.generate_personal_name(&PersonalNameContext::simple(id)),
+    };
+
+    (start..start.saturating_add(count))
+        .map(|id| match kind {
+            NameKind::Personal => personal(id),
+            NameKind::Compound => naming.generate_compound_name(id, 2),
+            NameKind::Settlement => place(id, PlaceType::Settlement),
+            NameKind::Natural => place(id, PlaceType::Natural),
+            NameKind::Landmark => place(id, PlaceType::Landmark),
+            NameKind::Region => place(id, PlaceType::Region),
+            NameKind::Epithet => {
+                let characteristic = CHARACTERISTICS[(id % CHARACTERISTICS.len() as u64) as usize];
+                let context = EpithetContext::new(id).with_characteristic(characteristic);
+                naming.generate_name_with_epithet(&personal(id), &context)
+            }
+        })
+        .collect()
+}
+
+/// `phyla dict`: print a dictionary.
+pub fn dict(format: OutputFormat, language: &Language, concepts: &[String]) {
+    print(format, &dictionary(language, concepts), |entries| {
+        dict_text(entries)
+    });
+}
+
+/// Render dictionary entries as aligned text.
+pub fn dict_text(entries: &[DictEntry]) -> String {
+    let width = entries.iter().map(|e| e.concept.len()).max().unwrap_or(0);
+    entries
+        .iter()
+        .map(|e| {
+            let line = format!("{:width$}  {}", e.concept, e.word, width = width);
+            match &e.etymology {
+                Etymology::Native => line,
+                Etymology::Innovation => format!("{}  (innovation)", line),
+                Etymology::Borrowed { donor, source_form } => {
+                    format!("{}  (borrowed from {} {})", line, donor, source_form)
+                }
+                Etymology::Inherited {
+                    source,
+                    source_form,
+                } => format!("{}  (from {} {})", line, source, source_form),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Build dictionary entries for concepts, or for the core vocabulary if none are given.
+pub fn dictionary(language: &Language, concepts: &[String]) -> Vec<DictEntry> {
+    let concepts: Vec<String> = if concepts.is_empty() {
+        CORE_VOCABULARY.iter().map(|c| c.to_string()).collect()
+    } else {
+        concepts.iter().map(|c| c.trim().to_lowercase()).collect()
+    };
+    concepts
+        .into_iter()
+        .map(|concept| DictEntry {
+            word: language.translate_word(&concept),
+            etymology: language.etymology(&concept),
+            concept,
+        })
+        .collect()
+}
+
+/// `phyla info`: describe a language.
+pub fn info(format: OutputFormat, language: &Language) {
+    print(format, &describe(language), info_text);
+}
+
+/// Render a language summary as text.
+pub fn info_text(info: &Info) -> String {
+    let list = |phonemes: &[String]| {
+        if phonemes.is_empty() {
+            "-".to_string()
+        } else {
+            phonemes.join(" ")
+        }
+    };
+    let culture = &info.culture;
+    [
+        format!("Language:          {}", info.id),
+        format!("Algorithm version: {}", info.algorithm_version),
+        format!("Geography:         {:?}", info.geography),
+        format!(
+            "Culture:           A {} O {} C {} E {} H {} Em {}",
+            culture.agreeableness,
+            culture.openness,
+            culture.conscientiousness,
+            culture.extraversion,
+            culture.honesty_humility,
+            culture.emotionality
+        ),
+        String::new(),
+        format!("Stops:             {}", list(&info.stops)),
+        format!("Fricatives:        {}", list(&info.fricatives)),
+        format!("Nasals:            {}", list(&info.nasals)),
+        format!("Liquids:           {}", list(&info.liquids)),
+        format!("Glides:            {}", list(&info.glides)),
+        format!("Vowels:            {}", list(&info.vowels)),
+        format!("Loan phonemes:     {}", list(&info.loan_phonemes)),
+        format!("Syllables:         {}", info.syllable_patterns.join(" ")),
+        String::new(),
+        format!("Word order:        {:?}", info.word_order),
+        format!("Morphology:        {:?}", info.morphology),
+        format!("Stress:            {:?}", info.stress),
+        format!("Name pattern:      {:?}", info.name_pattern),
+        format!("Combining rule:    {:?}", info.combining_rule),
+    ]
+    .join("\n")
+}
+
+/// Summarize a language's sounds and grammar.
+pub fn describe(language: &Language) -> Info {
+    let genome = &language.genome;
+    let inventory = &genome.phoneme_inventory;
+    let consonants = |list: &[phyla_lang::Consonant]| list.iter().map(|c| c.0.clone()).collect();
+
+    // Patterns are repeated in the genome to weight them; list each once
+    let mut syllable_patterns = Vec::new();
+    for pattern in genome.syllable_patterns.iter().map(|s| s.pattern()) {
+        if !syllable_patterns.contains(&pattern) {
+            syllable_patterns.push(pattern);
+        }
+    }
+
+    Info {
+        id: language.id.clone(),
+        algorithm_version: genome.algorithm_version.number(),
+        geography: language.geography(),
+        culture: *language.culture(),
+        stops: consonants(&inventory.stops),
+        fricatives: consonants(&inventory.fricatives),
+        nasals: consonants(&inventory.nasals),
+        liquids: consonants(&inventory.liquids),
+        glides: consonants(&inventory.glides),
+        vowels: inventory.vowels.iter().map(|v| v.0.clone()).collect(),
+        loan_phonemes: genome.loan_phonemes.clone(),
+        syllable_patterns,
+        word_order: genome.word_order,
+        morphology: genome.morphology_type,
+        stress: genome.prosody.stress_pattern,
+        name_pattern: language.naming.pattern,
+        combining_rule: language.naming.combining_rule,
+    }
+}
@@ -0,0 +1,191 @@
+//! `phyla`: generate and explore languages from the command line.
+//!
+//! Languages are stored in the library's versioned file format, so files written
+//! here can be loaded with `Language::load` and vice versa.
+
+mod commands;
+mod presets;
+mod repl;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use commands::NameKind;
+use presets::{GeographyArg, Preset};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Generate and explore procedural languages.
+#[derive(Debug, Parser)]
+#[command(name = "phyla", version)]
+struct Cli {
+    /// Output format
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    Text,
+    /// JSON, for scripts and other tools
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Create a language and save it to a file
+    New(NewArgs),
+    /// Translate words or phrases
+    Translate {
+        /// Language file
+        language: PathBuf,
+        /// Words or phrases to translate; quote a phrase to translate it as one
+        #[arg(required = true)]
+        text: Vec<String>,
+    },
+    /// Generate a batch of names
+    Names {
+        /// Language file
+        language: PathBuf,
+        /// Kind of name
+        #[arg(long, short, value_enum, default_value_t = NameKind::Personal)]
+        kind: NameKind,
+        /// Number of names
+        #[arg(long, short = 'n', default_value_t = 10)]
+        count: u64,
+        /// ID of the first name; names are numbered from here
+        #[arg(long, default_value_t = 0)]
+        start: u64,
+        /// Parent's name, for personal names in patronymic cultures
+        #[arg(long)]
+        parent: Option<String>,
+    },
+    /// Print a dictionary of the language
+    Dict {
+        /// Language file
+        language: PathBuf,
+        /// Concepts to include, separated by commas (default: the core vocabulary)
+        #[arg(long, value_delimiter = ',')]
+        concepts: Vec<String>,
+    },
+    /// Describe the language's sounds and grammar
+    Info {
+        /// Language file
+        language: PathBuf,
+    },
+    /// Start an interactive shell for tuning a language
+    Repl {
+        /// Language file to load
+        language: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct NewArgs {
+    /// File to save the language to
+    output: PathBuf,
+    /// Start from a ready-made culture and geography
+    #[arg(long, value_enum)]
+    preset: Option<Preset>,
+    /// Geography (default: the preset's, or plains)
+    #[arg(long, value_enum)]
+    geography: Option<GeographyArg>,
+    /// Seed for deterministic generation
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Language ID (default: derived from the seed)
+    #[arg(long)]
+    id: Option<String>,
+    #[command(flatten)]
+    traits: TraitArgs,
+}
+
+/// Personality trait scores, from 1 to 5.
+///
+/// Unset traits come from the preset, or default to 3.
+#[derive(Debug, Args)]
+struct TraitArgs {
+    /// Agreeableness (1-5)
+    #[arg(long)]
+    agreeableness: Option<f32>,
+    /// Openness (1-5)
+    #[arg(long)]
+    openness: Option<f32>,
+    /// Conscientiousness (1-5)
+    #[arg(long)]
+    conscientiousness: Option<f32>,
+    /// Extraversion (1-5)
+    #[arg(long)]
+    extraversion: Option<f32>,
+    /// Honesty-humility (1-5)
+    #[arg(long)]
+    honesty_humility: Option<f32>,
+    /// Emotionality (1-5)
+    #[arg(long)]
+    emotionality: Option<f32>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let format = cli.format;
+    match cli.command {
+        Command::New(args) => {
+            // Without a preset, every trait defaults to the middle of the scale
+            let preset = args.preset.unwrap_or(Preset::Plains);
+            let base = preset.culture();
+            let t = &args.traits;
+            let culture = phyla_lang::CulturalProfile::try_new(
+                t.agreeableness.unwrap_or(base.agreeableness),
+                t.openness.unwrap_or(base.openness),
+                t.conscientiousness.unwrap_or(base.conscientiousness),
+                t.extraversion.unwrap_or(base.extraversion),
+                t.honesty_humility.unwrap_or(base.honesty_humility),
+                t.emotionality.unwrap_or(base.emotionality),
+            )?;
+            let geography = args
+                .geography
+                .map(Into::into)
+                .unwrap_or_else(|| preset.geography());
+            commands::new(format, &args.output, culture, geography, args.seed, args.id)
+        }
+        Command::Translate { language, text } => {
+            commands::translate(format, &commands::load(&language)?, &text);
+            Ok(())
+        }
+        Command::Names {
+            language,
+            kind,
+            count,
+            start,
+            parent,
+        } => {
+            let language = commands::load(&language)?;
+            let names = commands::names(&language, kind, start, count, parent.as_deref());
+            commands::print_list(format, &names);
+            Ok(())
+        }
+        Command::Dict { language, concepts } => {
+            let language = commands::load(&language)?;
+            commands::dict(format, &language, &concepts);
+            Ok(())
+        }
+        Command::Info { language } => {
+            commands::info(format, &commands::load(&language)?);
+            Ok(())
+        }
+        Command::Repl { language } => repl::run(language),
+    }
+}
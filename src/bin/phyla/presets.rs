@@ -0,0 +1,71 @@
+//! Ready-made cultures for `phyla new`.
+
+use clap::ValueEnum;
+use phyla_lang::{CulturalProfile, Geography};
+
+/// A named starting point for a language.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Preset {
+    /// Cooperative, expressive seafarers
+    Coastal,
+    /// Disciplined, reserved highland clans
+    Mountain,
+    /// Proud, enduring desert nomads
+    Desert,
+    /// Gentle, sensitive forest dwellers
+    Forest,
+    /// Balanced farming folk
+    Plains,
+    /// Orderly river-valley city builders
+    River,
+}
+
+impl Preset {
+    /// The preset's cultural profile.
+    pub fn culture(self) -> CulturalProfile {
+        match self {
+            Preset::Coastal => CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0),
+            Preset::Mountain => CulturalProfile::new(1.0, 2.0, 4.0, 2.0, 3.0, 2.0),
+            Preset::Desert => CulturalProfile::new(2.0, 2.0, 3.0, 3.0, 2.0, 2.0),
+            Preset::Forest => CulturalProfile::new(4.5, 3.5, 2.5, 2.0, 3.5, 4.0),
+            Preset::Plains => CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 3.0, 3.0),
+            Preset::River => CulturalProfile::new(3.0, 2.5, 4.5, 3.5, 3.0, 3.0),
+        }
+    }
+
+    /// The preset's geography.
+    pub fn geography(self) -> Geography {
+        match self {
+            Preset::Coastal => Geography::Coastal,
+            Preset::Mountain => Geography::Mountains,
+            Preset::Desert => Geography::Desert,
+            Preset::Forest => Geography::Forest,
+            Preset::Plains => Geography::Plains,
+            Preset::River => Geography::RiverValley,
+        }
+    }
+}
+
+/// A geography, as given on the command line.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum GeographyArg {
+    Mountains,
+    Coastal,
+    Desert,
+    Forest,
+    Plains,
+    RiverValley,
+}
+
+impl From<GeographyArg> for Geography {
+    fn from(arg: GeographyArg) -> Self {
+        match arg {
+            GeographyArg::Mountains => Geography::Mountains,
+            GeographyArg::Coastal => Geography::Coastal,
+            GeographyArg::Desert => Geography::Desert,
+            GeographyArg::Forest => Geography::Forest,
+            GeographyArg::Plains => Geography::Plains,
+            GeographyArg::RiverValley => Geography::RiverValley,
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! `phyla repl`: an interactive shell for tuning a language.
+//!
+//! Changing a trait regenerates the language at once and shows how a watch list
+//! of words and a few names changed, so a culture can be tuned in seconds.
+
+use crate::commands::{self, NameKind};
+use crate::presets::{GeographyArg, Preset};
+use clap::ValueEnum;
+use phyla_lang::{CulturalProfile, Language};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+/// Words watched until the user picks their own.
+const DEFAULT_WATCH: &[&str] = &["water", "fire", "house", "mother", "sun", "king"];
+
+/// Number of names shown when a setting changes.
+const WATCHED_NAMES: u64 = 3;
+
+const HELP: &str = "\
+Commands:
+  load <file>                 load a language file
+  save [file]                 save the language (to the file it came from by default)
+  new <preset> [seed]         start from a preset: coastal, mountain, desert, forest, plains, river
+  t, translate <text>         translate a word or phrase
+  set <setting> <value>       change a trait (e.g. set openness 4.5), geography or seed,
+                              and show the watch list before and after
+  watch [words...]            add words to the watch list, or show it
+  unwatch <words...>          remove words from the watch list
+  names [kind] [count]        generate names: personal, compound, settlement, natural,
+                              landmark, region, epithet
+  dict [concepts...]          show a dictionary
+  info                        describe the language
+  help                        show this help
+  quit                        leave";
+
+/// The result of one command.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Step {
+    /// Print this and read the next command
+    Continue(String),
+    /// Leave the shell
+    Quit,
+}
+
+/// The state of an interactive session.
+pub struct Repl {
+    language: Option<Language>,
+    path: Option<PathBuf>,
+    watch: Vec<String>,
+}
+
+impl Repl {
+    /// Start a session with no language loaded.
+    pub fn new() -> Self {
+        Self {
+            language: None,
+            path: None,
+            watch: DEFAULT_WATCH.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    /// Run one command line.
+    pub fn execute(&mut self, line: &str) -> Result<Step, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = words.split_first() else {
+            return Ok(Step::Continue(String::new()));
+        };
+
+        let output = match command {
+            "help" | "?" => HELP.to_string(),
+            "quit" | "exit" => return Ok(Step::Quit),
+            "load" => self.load(args)?,
+            "save" => self.save(args)?,
+            "new" => self.new_language(args)?,
+            "t" | "translate" => {
+                let text = args.join(" ");
+                if text.is_empty() {
+                    return Err("usage: translate <text>".to_string());
+                }
+                commands::translate_text(self.language()?, &text)
+            }
+            "set" => self.set(args)?,
+            "watch" => {
+                for word in args {
+                    let word = word.to_lowercase();
+                    if !self.watch.contains(&word) {
+                        self.watch.push(word);
+                    }
+                }
+                self.show_watch()?
+            }
+            "unwatch" => {
+                self.watch
+                    .retain(|w| !args.iter().any(|a| a.eq_ignore_ascii_case(w)));
+                self.show_watch()?
+            }
+            "names" => self.names(args)?,
+            "dict" => {
+                let concepts: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                commands::dict_text(&commands::dictionary(self.language()?, &concepts))
+            }
+            "info" => commands::info_text(&commands::describe(self.language()?)),
+            _ => return Err(format!("unknown command '{}'; try 'help'", command)),
+        };
+        Ok(Step::Continue(output))
+    }
+
+    fn language(&self) -> Result<&Language, String> {
+        self.language
+            .as_ref()
+            .ok_or_else(|| "no language loaded; use 'load <file>' or 'new <preset>'".to_string())
+    }
+
+    fn load(&mut self, args: &[&str]) -> Result<String, String> {
+        if args.is_empty() {
+            return Err("usage: load <file>".to_string());
+        }
+        self.open(PathBuf::from(args.join(" ")))
+    }
+
+    /// Load a language file, making it the file `save` writes to.
+    pub fn open(&mut self, path: PathBuf) -> Result<String, String> {
+        let language = commands::load(&path).map_err(|e| e.to_string())?;
+        let message = format!("Loaded {} from {}", language.id, path.display());
+        self.language = Some(language);
+        self.path = Some(path);
+        Ok(message)
+    }
+
+    fn save(&mut self, args: &[&str]) -> Result<String, String> {
+        let path = match args {
+            [] => self
+                .path
+                .clone()
+                .ok_or("no file to save to; use 'save <file>'")?,
+            [path] => PathBuf::from(path),
+            _ => return Err("usage: save [file]".to_string()),
+        };
+        let language = self.language()?;
+        commands::save(language, &path).map_err(|e| e.to_string())?;
+        let message = format!("Saved {} to {}", language.id, path.display());
+        self.path = Some(path);
+        Ok(message)
+    }
+
+    fn new_language(&mut self, args: &[&str]) -> Result<String, String> {
+        let (preset, seed) = match args {
+            [preset] => (*preset, "0"),
+            [preset, seed] => (*preset, *seed),
+            _ => return Err("usage: new <preset> [seed]".to_string()),
+        };
+        let preset = Preset::from_str(preset, true)?;
+        let seed = seed
+            .parse()
+            .map_err(|_| format!("invalid seed '{}'", seed))?;
+
+        let language = Language::from_culture(preset.culture(), preset.geography(), seed);
+        let message = format!("Created {}", language.id);
+        self.language = Some(language);
+        self.path = None;
+        Ok(message)
+    }
+
+    /// Change a setting, regenerate the language and show what changed.
+    fn set(&mut self, args: &[&str]) -> Result<String, String> {
+        let [setting, value] = args else {
+            return Err("usage: set <setting> <value>".to_string());
+        };
+        let old = self.language()?;
+        let mut culture = *old.culture();
+        let mut geography = old.geography();
+        let mut seed = old.genome.seed;
+
+        match setting.to_lowercase().replace('-', "_").as_str() {
+            "geography" => geography = GeographyArg::from_str(value, true)?.into(),
+            "seed" => {
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("invalid seed '{}'", value))?
+            }
+            name => {
+                let score = value
+                    .parse()
+                    .map_err(|_| format!("invalid score '{}'", value))?;
+                *trait_mut(&mut culture, name)? = score;
+            }
+        }
+
+        let mut language = Language::try_from_culture_with_version(
+            culture,
+            geography,
+            seed,
+            old.genome.algorithm_version,
+        )
+        .map_err(|e| e.to_string())?;
+        if old.id != format!("lang_{}", old.genome.seed) {
+            language.id = old.id.clone();
+        }
+
+        let mut lines = self.compare(old, &language);
+        if has_history(old) {
+            lines.push(
+                "note: the language was regenerated from its culture; its dialect, drift \
+                 and contact history was dropped"
+                    .to_string(),
+            );
+        }
+        self.language = Some(language);
+        Ok(lines.join("\n"))
+    }
+
+    /// Show the watch list and names of two versions of a language side by side.
+    fn compare(&self, before: &Language, after: &Language) -> Vec<String> {
+        let width = self.watch.iter().map(|w| w.len()).max().unwrap_or(0).max(5);
+        let mut lines: Vec<String> = self
+            .watch
+            .iter()
+            .map(|word| {
+                let old = before.translate_word(word);
+                let new = after.translate_word(word);
+                let mark = if old == new { "  (same)" } else { "" };
+                format!("{:width$}  {} -> {}{}", word, old, new, mark, width = width)
+            })
+            .collect();
+
+        let names =
+            |language| commands::names(language, NameKind::Personal, 0, WATCHED_NAMES, None);
+        lines.push(format!(
+            "{:width$}  {} -> {}",
+            "names",
+            names(before).join(", "),
+            names(after).join(", "),
+            width = width
+        ));
+        lines
+    }
+
+    fn show_watch(&self) -> Result<String, String> {
+        let language = self.language()?;
+        let width = self.watch.iter().map(|w| w.len()).max().unwrap_or(0);
+        Ok(self
+            .watch
+            .iter()
+            .map(|w| {
+                format!(
+                    "{:width$}  {}",
+                    w,
+                    language.translate_word(w),
+                    width = width
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn names(&self, args: &[&str]) -> Result<String, String> {
+        let (kind, count) = match args {
+            [] => (NameKind::Personal, 10),
+            [kind] => (NameKind::from_str(kind, true)?, 10),
+            [kind, count] => (
+                NameKind::from_str(kind, true)?,
+                count
+                    .parse()
+                    .map_err(|_| format!("invalid count '{}'", count))?,
+            ),
+            _ => return Err("usage: names [kind] [count]".to_string()),
+        };
+        Ok(commands::names(self.language()?, kind, 0, count, None).join("\n"))
+    }
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Look up a personality trait by name.
+fn trait_mut<'a>(culture: &'a mut CulturalProfile, name: &str) -> Result<&'a mut f32, String> {
+    Ok(match name {
+        "agreeableness" | "a" => &mut culture.agreeableness,
+        "openness" | "o" => &mut culture.openness,
+        "conscientiousness" | "c" => &mut culture.conscientiousness,
+        "extraversion" | "e" => &mut culture.extraversion,
+        "honesty_humility" | "honesty" | "h" => &mut culture.honesty_humility,
+        "emotionality" | "em" => &mut culture.emotionality,
+        _ => return Err(format!("unknown setting '{}'", name)),
+    })
+}
+
+/// Check whether a language has changes that regenerating it from culture loses.
+fn has_history(language: &Language) -> bool {
+    let genome = &language.genome;
+    !genome.sound_changes.is_empty()
+        || !genome.lexical_shifts.is_empty()
+        || !genome.lexicon.is_empty()
+        || !genome.loan_phonemes.is_empty()
+        || !genome.affixes.is_empty()
+}
+
+/// Run the shell on standard input until it ends or the user quits.
+pub fn run(path: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut repl = Repl::new();
+    if let Some(path) = path {
+        println!("{}", repl.open(path)?);
+    }
+
+    let interactive = std::io::stdin().is_terminal();
+    if interactive {
+        println!("phyla shell; type 'help' for commands");
+    }
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        if interactive {
+            print!("phyla> ");
+            std::io::stdout().flush()?;
+        }
+        let Some(line) = lines.next() else {
+            return Ok(());
+        };
+        match repl.execute(&line?) {
+            Ok(Step::Continue(output)) if output.is_empty() => {}
+            Ok(Step::Continue(output)) => println!("{}", output),
+            Ok(Step::Quit) => return Ok(()),
+            Err(error) => eprintln!("error: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(repl: &mut Repl, line: &str) -> String {
+        match repl.execute(line).unwrap() {
+            Step::Continue(output) => output,
+            Step::Quit => panic!("unexpected quit"),
+        }
+    }
+
+    #[test]
+    fn test_set_shows_before_and_after() {
+        let mut repl = Repl::new();
+        output(&mut repl, "new coastal 42");
+        let before = repl.language.as_ref().unwrap().translate_word("water");
+
+        let report = output(&mut repl, "set openness 4.5");
+        let after = repl.language.as_ref().unwrap().translate_word("water");
+        assert_eq!(repl.language.as_ref().unwrap().culture().openness, 4.5);
+        let water = report.lines().find(|l| l.starts_with("water ")).unwrap();
+        assert!(water.contains(&format!("{} -> {}", before, after)));
+        assert!(report.contains("names"));
+    }
+
+    #[test]
+    fn test_invalid_input_is_an_error() {
+        let mut repl = Repl::new();
+        assert!(repl.execute("translate water").is_err());
+
+        output(&mut repl, "new plains");
+        assert!(repl.execute("set openness 9").is_err());
+        assert!(repl.execute("set wisdom 3").is_err());
+        assert!(repl.execute("names dragons").is_err());
+        assert!(repl.execute("frobnicate").is_err());
+        assert_eq!(repl.language.as_ref().unwrap().culture().openness, 3.0);
+    }
+
+    #[test]
+    fn test_watch_and_names() {
+        let mut repl = Repl::new();
+        output(&mut repl, "new forest 7");
+        let language =
+            Language::from_culture(Preset::Forest.culture(), Preset::Forest.geography(), 7);
+
+        let watched = output(&mut repl, "watch river");
+        assert!(watched.contains(&language.translate_word("river")));
+        let unwatched = output(&mut repl, "unwatch water river");
+        assert!(!unwatched.contains("river"));
+        assert!(!unwatched.contains("water"));
+
+        let names = output(&mut repl, "names compound 2");
+        assert_eq!(
+            names,
+            commands::names(&language, NameKind::Compound, 0, 2, None).join("\n")
+        );
+        assert_eq!(repl.execute("quit").unwrap(), Step::Quit);
+    }
+}
@@ -0,0 +1,304 @@
+//! Building languages with individual parameters pinned.
+
+#[cfg(feature = "std")]
+use crate::cache::CacheConfig;
+use crate::culture::{CulturalProfile, Geography};
+use crate::error::Error;
+use crate::genome::{AlgorithmVersion, LinguisticGenome, MorphologyType, WordOrder};
+use crate::language::Language;
+use crate::morphology::CombiningRule;
+use crate::naming::{
+    birth_order::BirthOrderNaming, gender::GenderMarking, surname::SurnameInheritance, NamePattern,
+};
+use crate::phonology::{PhonemeInventory, ProsodicSystem, SyllableStructure};
+use alloc::vec::Vec;
+
+/// Builds a [`Language`], deriving every parameter from culture and geography
+/// unless it has been overridden.
+///
+/// Overrides are applied to the genome before the naming system is built, so
+/// names use the same sounds and patterns as words.
+///
+/// # Example
+///
+/// ```
+/// use phyla_lang::{CulturalProfile, Geography, Language, NamePattern, WordOrder};
+///
+/// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+/// let language = Language::builder(culture, Geography::Coastal, 12345)
+///     .with_word_order(WordOrder::SOV)
+///     .with_name_pattern(NamePattern::Patronymic)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(language.word_order(), WordOrder::SOV);
+/// assert_eq!(language.naming.pattern, NamePattern::Patronymic);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LanguageBuilder {
+    culture: CulturalProfile,
+    geography: Geography,
+    seed: u64,
+    algorithm_version: AlgorithmVersion,
+    phoneme_inventory: Option<PhonemeInventory>,
+    syllable_patterns: Option<Vec<SyllableStructure>>,
+    prosody: Option<ProsodicSystem>,
+    word_order: Option<WordOrder>,
+    morphology_type: Option<MorphologyType>,
+    name_pattern: Option<NamePattern>,
+    combining_rule: Option<CombiningRule>,
+    surname_inheritance: Option<SurnameInheritance>,
+    gender_marking: Option<GenderMarking>,
+    birth_order_naming: Option<BirthOrderNaming>,
+    #[cfg(feature = "std")]
+    cache: Option<CacheConfig>,
+}
+
+impl LanguageBuilder {
+    /// Start building a language from a cultural profile and geography.
+    pub fn new(culture: CulturalProfile, geography: Geography, seed: u64) -> Self {
+        Self {
+            culture,
+            geography,
+            seed,
+            algorithm_version: AlgorithmVersion::LATEST,
+            phoneme_inventory: None,
+            syllable_patterns: None,
+            prosody: None,
+            word_order: None,
+            morphology_type: None,
+            name_pattern: None,
+            combining_rule: None,
+            surname_inheritance: None,
+            gender_marking: None,
+            birth_order_naming: None,
+            #[cfg(feature = "std")]
+            cache: None,
+        }
+    }
+
+    /// Use a specific version of the generation algorithms.
+    pub fn with_algorithm_version(mut self, algorithm_version: AlgorithmVersion) -> Self {
+        self.algorithm_version = algorithm_version;
+        self
+    }
+
+    /// Use this phoneme inventory.
+    pub fn with_phoneme_inventory(mut self, inventory: PhonemeInventory) -> Self {
+        self.phoneme_inventory = Some(inventory);
+        self
+    }
+
+    /// Use these syllable patterns.
+    pub fn with_syllable_patterns(mut self, patterns: Vec<SyllableStructure>) -> Self {
+        self.syllable_patterns = Some(patterns);
+        self
+    }
+
+    /// Use this prosodic system.
+    pub fn with_prosody(mut self, prosody: ProsodicSystem) -> Self {
+        self.prosody = Some(prosody);
+        self
+    }
+
+    /// Use this word order.
+    pub fn with_word_order(mut self, word_order: WordOrder) -> Self {
+        self.word_order = Some(word_order);
+        self
+    }
+
+    /// Use this morphological type.
+    pub fn with_morphology(mut self, morphology_type: MorphologyType) -> Self {
+        self.morphology_type = Some(morphology_type);
+        self
+    }
+
+    /// Use this naming pattern.
+    pub fn with_name_pattern(mut self, pattern: NamePattern) -> Self {
+        self.name_pattern = Some(pattern);
+        self
+    }
+
+    /// Use this rule for combining morphemes into names.
+    pub fn with_combining_rule(mut self, rule: CombiningRule) -> Self {
+        self.combining_rule = Some(rule);
+        self
+    }
+
+    /// Use this way of passing down family names.
+    pub fn with_surname_inheritance(mut self, inheritance: SurnameInheritance) -> Self {
+        self.surname_inheritance = Some(inheritance);
+        self
+    }
+
+    /// Mark gender in personal names this way.
+    pub fn with_gender_marking(mut self, marking: GenderMarking) -> Self {
+        self.gender_marking = Some(marking);
+        self
+    }
+
+    /// Show birth order in personal names this way.
+    pub fn with_birth_order_naming(mut self, birth_order_naming: BirthOrderNaming) -> Self {
+        self.birth_order_naming = Some(birth_order_naming);
+        self
+    }
+
+    /// Cache translated words with these settings.
+    #[cfg(feature = "std")]
+    pub fn with_cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(config);
+        self
+    }
+
+    /// Build the language.
+    ///
+    /// Returns an error if the cultural profile is invalid or the overrides leave
+    /// the genome unable to generate words, e.g. an inventory without vowels.
+    pub fn build(self) -> Result<Language, Error> {
+        self.culture.validate()?;
+
+        let mut genome = LinguisticGenome::from_culture_with_version(
+            self.culture,
+            self.geography,
+            self.seed,
+            self.algorithm_version,
+        );
+        if let Some(inventory) = self.phoneme_inventory {
+            genome.phoneme_inventory = inventory;
+        }
+        if let Some(patterns) = self.syllable_patterns {
+            genome.syllable_patterns = patterns;
+        }
+        if let Some(prosody) = self.prosody {
+            genome.prosody = prosody;
+        }
+        if let Some(word_order) = self.word_order {
+            genome.word_order = word_order;
+        }
+        if let Some(morphology_type) = self.morphology_type {
+            genome.morphology_type = morphology_type;
+        }
+
+        let mut language = Language::try_from_genome(genome, self.culture, self.geography)?;
+        if let Some(pattern) = self.name_pattern {
+            language.naming.pattern = pattern;
+        }
+        if let Some(rule) = self.combining_rule {
+            language.naming.combining_rule = rule;
+        }
+        if let Some(inheritance) = self.surname_inheritance {
+            language.naming.surname_inheritance = inheritance;
+        }
+        if let Some(marking) = self.gender_marking {
+            language.naming.gender_marking = marking;
+        }
+        if let Some(birth_order_naming) = self.birth_order_naming {
+            language.naming.birth_order_naming = birth_order_naming;
+        }
+        #[cfg(feature = "std")]
+        if let Some(config) = self.cache {
+            language.set_cache_config(config);
+        }
+        Ok(language)
+    }
+}
+
+impl Language {
+    /// Start building a language whose parameters can be overridden.
+    pub fn builder(culture: CulturalProfile, geography: Geography, seed: u64) -> LanguageBuilder {
+        LanguageBuilder::new(culture, geography, seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::phonology::{Consonant, Vowel};
+
+    fn culture() -> CulturalProfile {
+        CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0)
+    }
+
+    #[test]
+    fn test_no_overrides_matches_from_culture() {
+        let built = Language::builder(culture(), Geography::Coastal, 12345)
+            .build()
+            .unwrap();
+        let plain = Language::from_culture(culture(), Geography::Coastal, 12345);
+
+        assert_eq!(built.id, plain.id);
+        assert_eq!(
+            built.translate_phrase("I bring the beer quickly"),
+            plain.translate_phrase("I bring the beer quickly")
+        );
+        assert_eq!(
+            built.naming.generate_compound_name(3, 2),
+            plain.naming.generate_compound_name(3, 2)
+        );
+    }
+
+    #[test]
+    fn test_inventory_override_reaches_names() {
+        let mut inventory =
+            LinguisticGenome::from_culture(culture(), Geography::Coastal, 1).phoneme_inventory;
+        inventory.stops = vec![Consonant::new("k")];
+        inventory.fricatives.clear();
+        inventory.nasals = vec![Consonant::new("n")];
+        inventory.liquids.clear();
+        inventory.glides.clear();
+        inventory.vowels = vec![Vowel::new("a"), Vowel::new("u")];
+
+        let language = Language::builder(culture(), Geography::Coastal, 12345)
+            .with_phoneme_inventory(inventory)
+            .with_syllable_patterns(vec![SyllableStructure::CV])
+            .with_combining_rule(CombiningRule::Concatenate)
+            .build()
+            .unwrap();
+
+        let allowed = |text: &str| text.to_lowercase().chars().all(|c| "knau".contains(c));
+        assert!(allowed(&language.translate_word("house")));
+        for id in 0..10 {
+            assert!(allowed(&language.naming.generate_simple_name(id)));
+            assert!(allowed(&language.naming.generate_compound_name(id, 2)));
+        }
+        assert_eq!(
+            language.naming.genome.syllable_patterns,
+            [SyllableStructure::CV]
+        );
+    }
+
+    #[test]
+    fn test_overrides_survive_contact() {
+        let donor = Language::from_culture(
+            CulturalProfile::new(2.0, 4.0, 3.0, 3.0, 2.0, 2.0),
+            Geography::Desert,
+            7,
+        );
+        let mut language = Language::builder(culture(), Geography::Coastal, 12345)
+            .with_name_pattern(NamePattern::Elaborate)
+            .with_combining_rule(CombiningRule::Hyphenated)
+            .with_word_order(WordOrder::VSO)
+            .build()
+            .unwrap();
+        language.borrow_phonemes(&donor, 1.0);
+
+        assert_eq!(language.naming.pattern, NamePattern::Elaborate);
+        assert!(matches!(
+            language.naming.combining_rule,
+            CombiningRule::Hyphenated
+        ));
+        assert_eq!(language.naming.genome.word_order, WordOrder::VSO);
+        assert_eq!(
+            language.evolve(300, 1).naming.pattern,
+            NamePattern::Elaborate
+        );
+    }
+
+    #[test]
+    fn test_invalid_overrides_are_errors() {
+        let result = Language::builder(culture(), Geography::Coastal, 12345)
+            .with_syllable_patterns(Vec::new())
+            .build();
+        assert!(matches!(result, Err(Error::InvalidGenome(_))));
+    }
+}
@@ -0,0 +1,457 @@
+//! A bounded, sharded cache of translated words.
+//!
+//! Words are spread over several independently locked shards, so threads
+//! translating different concepts rarely wait on each other. Each shard holds a
+//! fixed share of the capacity and evicts by its own policy when it is full.
+//! Shards keep their words in eviction order, so finding the word to drop takes
+//! logarithmic time however large the cache is.
+
+use crate::memory::{btree_map_size, HeapSize};
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::BuildHasher;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Which word a full cache drops to make room for a new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// Drop the least recently used word
+    #[default]
+    LeastRecentlyUsed,
+    /// Drop the least frequently used word, the older one on a tie
+    LeastFrequentlyUsed,
+}
+
+/// Settings for a language's word cache.
+///
+/// Every lookup and insert locks one shard and takes time logarithmic in the
+/// number of words in it, so eviction stays cheap however large the capacity.
+/// Each cached word keeps two copies of its concept, one to look it up and one
+/// to order it for eviction. More shards let more threads translate at once.
+///
+/// # Example
+///
+/// ```
+/// use phyla_lang::{CacheConfig, CulturalProfile, EvictionPolicy, Geography, Language};
+///
+/// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+/// let mut language = Language::from_culture(culture, Geography::Coastal, 12345);
+/// language.set_cache_config(
+///     CacheConfig::new(1000).with_policy(EvictionPolicy::LeastFrequentlyUsed),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Most words kept at once; 0 disables caching
+    pub capacity: usize,
+    /// Number of independently locked shards
+    pub shards: usize,
+    /// How full shards make room
+    pub policy: EvictionPolicy,
+}
+
+impl CacheConfig {
+    /// Words cached by default.
+    pub const DEFAULT_CAPACITY: usize = 4096;
+    /// Shards used by default.
+    pub const DEFAULT_SHARDS: usize = 16;
+
+    /// Cache up to `capacity` words with the default shards and policy.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Cache nothing; every translation generates its word again.
+    pub fn disabled() -> Self {
+        Self::new(0)
+    }
+
+    /// Use a different eviction policy.
+    pub fn with_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Use a different number of shards.
+    pub fn with_shards(mut self, shards: usize) -> Self {
+        self.shards = shards;
+        self
+    }
+
+    /// Whether words are cached at all.
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: Self::DEFAULT_CAPACITY,
+            shards: Self::DEFAULT_SHARDS,
+            policy: EvictionPolicy::default(),
+        }
+    }
+}
+
+/// Counters describing how well a cache is working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that had to generate the word
+    pub misses: u64,
+    /// Words dropped to make room
+    pub evictions: u64,
+    /// Words cached now
+    pub len: usize,
+    /// Most words the cache will hold
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Fraction of lookups answered from the cache, or 0 before any lookup.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// A cached word and how it has been used.
+#[derive(Debug)]
+struct Entry {
+    word: String,
+    /// Shard clock reading at the last use
+    last_used: u64,
+    /// Number of uses, including the insert
+    uses: u64,
+}
+
+impl Entry {
+    /// Where the entry sorts in eviction order; the first is dropped first.
+    ///
+    /// Clock readings are never reused, so no two entries of a shard share a
+    /// rank.
+    fn rank(&self, policy: EvictionPolicy) -> (u64, u64) {
+        match policy {
+            EvictionPolicy::LeastRecentlyUsed => (0, self.last_used),
+            EvictionPolicy::LeastFrequentlyUsed => (self.uses, self.last_used),
+        }
+    }
+}
+
+/// One independently locked part of the cache.
+#[derive(Debug, Default)]
+struct Shard {
+    entries: HashMap<String, Entry>,
+    /// Concepts by the rank of their entries
+    order: BTreeMap<(u64, u64), String>,
+    capacity: usize,
+    policy: EvictionPolicy,
+    /// Advances on every use, to order entries by recency
+    clock: u64,
+}
+
+impl Shard {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Record a use of a cached word and return the word.
+    fn touch(&mut self, concept: &str) -> Option<&str> {
+        let now = self.tick();
+        let entry = self.entries.get_mut(concept)?;
+        let key = self.order.remove(&entry.rank(self.policy));
+        entry.last_used = now;
+        entry.uses += 1;
+        if let Some(key) = key {
+            self.order.insert(entry.rank(self.policy), key);
+        }
+        Some(&entry.word)
+    }
+
+    /// Drop the entry the policy ranks first.
+    fn evict(&mut self) -> bool {
+        match self.order.pop_first() {
+            Some((_, concept)) => self.entries.remove(&concept).is_some(),
+            None => false,
+        }
+    }
+}
+
+/// The word cache of a [`Language`](crate::Language).
+#[derive(Debug)]
+pub(crate) struct WordCache {
+    config: CacheConfig,
+    shards: Box<[Mutex<Shard>]>,
+    hasher: RandomState,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl WordCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        // Never more shards than words, and at least one shard when enabled
+        let count = if config.is_enabled() {
+            config.shards.clamp(1, config.capacity)
+        } else {
+            0
+        };
+        let shards = (0..count)
+            .map(|i| {
+                // Spread the capacity exactly, giving the remainder to the first shards
+                let capacity = config.capacity / count + usize::from(i < config.capacity % count);
+                Mutex::new(Shard {
+                    capacity,
+                    policy: config.policy,
+                    ..Shard::default()
+                })
+            })
+            .collect();
+
+        Self {
+            config,
+            shards,
+            hasher: RandomState::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn config(&self) -> CacheConfig {
+        self.config
+    }
+
+    fn shard(&self, concept: &str) -> Option<&Mutex<Shard>> {
+        if self.shards.is_empty() {
+            return None;
+        }
+        let index = self.hasher.hash_one(concept) % self.shards.len() as u64;
+        Some(&self.shards[index as usize])
+    }
+
+    /// Whether words are cached at all.
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.shards.is_empty()
+    }
+
+    /// Write a cached word to `out`, recording a hit or a miss.
+    ///
+    /// Returns `None` if the word is not cached.
+    pub(crate) fn get_into<W: fmt::Write>(
+        &self,
+        concept: &str,
+        out: &mut W,
+    ) -> Option<fmt::Result> {
+        let shard = self.shard(concept)?;
+        let mut shard = shard.lock().unwrap();
+        match shard.touch(concept) {
+            Some(word) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(out.write_str(word))
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Cache a word, evicting another if its shard is full.
+    ///
+    /// A word cached again keeps its use count and recency.
+    pub(crate) fn insert(&self, concept: &str, word: &str) {
+        let Some(shard) = self.shard(concept) else {
+            return;
+        };
+        let mut shard = shard.lock().unwrap();
+        if let Some(entry) = shard.entries.get_mut(concept) {
+            entry.word = word.to_string();
+            return;
+        }
+
+        let now = shard.tick();
+        if shard.entries.len() >= shard.capacity && shard.evict() {
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        let entry = Entry {
+            word: word.to_string(),
+            last_used: now,
+            uses: 1,
+        };
+        let rank = entry.rank(shard.policy);
+        shard.order.insert(rank, concept.to_string());
+        shard.entries.insert(concept.to_string(), entry);
+    }
+
+    /// Drop every word. The statistics are kept.
+    pub(crate) fn clear(&self) {
+        for shard in self.shards.iter() {
+            let mut shard = shard.lock().unwrap();
+            shard.entries.clear();
+            shard.order.clear();
+        }
+    }
+
+    /// Number of words cached.
+    pub(crate) fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().entries.len())
+            .sum()
+    }
+
+    /// Bytes used by the shards and their words.
+    pub(crate) fn heap_size(&self) -> usize {
+        let shards = self.shards.iter().map(|shard| {
+            let shard = shard.lock().unwrap();
+            // One control byte per bucket besides the entry itself
+            shard.entries.capacity() * (size_of::<(String, Entry)>() + 1)
+                + shard
+                    .entries
+                    .iter()
+                    .map(|(concept, entry)| concept.heap_size() + entry.word.heap_size())
+                    .sum::<usize>()
+                + btree_map_size::<(u64, u64), String>(shard.order.len())
+                + shard.order.values().map(HeapSize::heap_size).sum::<usize>()
+        });
+        self.shards.len() * size_of::<Mutex<Shard>>() + shards.sum::<usize>()
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            len: self.len(),
+            capacity: self.config.capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(cache: &WordCache, concepts: &[&str]) {
+        for concept in concepts {
+            cache.insert(concept, &concept.to_uppercase());
+        }
+    }
+
+    fn get(cache: &WordCache, concept: &str) -> Option<String> {
+        let mut word = String::new();
+        cache.get_into(concept, &mut word).map(|_| word)
+    }
+
+    #[test]
+    fn test_hits_and_misses() {
+        let cache = WordCache::new(CacheConfig::new(8));
+        assert_eq!(get(&cache, "water"), None);
+        cache.insert("water", "miili");
+        assert_eq!(get(&cache, "water").as_deref(), Some("miili"));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.len), (1, 1, 1));
+        assert_eq!(stats.hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_capacity_is_never_exceeded() {
+        let cache = WordCache::new(CacheConfig::new(10).with_shards(4));
+        let concepts: Vec<String> = (0..100).map(|i| format!("concept{}", i)).collect();
+        for concept in &concepts {
+            cache.insert(concept, concept);
+        }
+
+        let stats = cache.stats();
+        assert!(stats.len <= 10);
+        assert_eq!(stats.evictions, 100 - stats.len as u64);
+    }
+
+    #[test]
+    fn test_lru_drops_least_recent() {
+        let cache = WordCache::new(CacheConfig::new(2).with_shards(1));
+        fill(&cache, &["a", "b"]);
+        get(&cache, "a");
+        fill(&cache, &["c"]);
+
+        assert!(get(&cache, "a").is_some());
+        assert!(get(&cache, "b").is_none());
+        assert!(get(&cache, "c").is_some());
+    }
+
+    #[test]
+    fn test_lfu_drops_least_frequent() {
+        let config = CacheConfig::new(2)
+            .with_shards(1)
+            .with_policy(EvictionPolicy::LeastFrequentlyUsed);
+        let cache = WordCache::new(config);
+        fill(&cache, &["a", "b"]);
+        get(&cache, "b");
+        get(&cache, "a");
+        get(&cache, "a");
+        fill(&cache, &["c"]);
+
+        assert!(get(&cache, "a").is_some());
+        assert!(get(&cache, "b").is_none());
+    }
+
+    #[test]
+    fn test_large_shard_evicts_in_order() {
+        let cache = WordCache::new(CacheConfig::new(1000).with_shards(1));
+        let concepts: Vec<String> = (0..1500).map(|i| format!("concept{}", i)).collect();
+        for concept in &concepts[..1000] {
+            cache.insert(concept, concept);
+        }
+        for concept in &concepts[..500] {
+            get(&cache, concept);
+        }
+        for concept in &concepts[1000..] {
+            cache.insert(concept, concept);
+        }
+
+        assert_eq!(cache.stats().evictions, 500);
+        assert!(concepts[..500].iter().all(|c| get(&cache, c).is_some()));
+        assert!(concepts[500..1000].iter().all(|c| get(&cache, c).is_none()));
+    }
+
+    #[test]
+    fn test_reinserting_keeps_uses() {
+        let config = CacheConfig::new(2)
+            .with_shards(1)
+            .with_policy(EvictionPolicy::LeastFrequentlyUsed);
+        let cache = WordCache::new(config);
+        fill(&cache, &["a", "b"]);
+        get(&cache, "a");
+        get(&cache, "a");
+        fill(&cache, &["a"]);
+        get(&cache, "b");
+        fill(&cache, &["c"]);
+
+        assert_eq!(get(&cache, "a").as_deref(), Some("A"));
+        assert!(get(&cache, "b").is_none());
+    }
+
+    #[test]
+    fn test_disabled_cache_stores_nothing() {
+        let cache = WordCache::new(CacheConfig::disabled());
+        fill(&cache, &["a"]);
+
+        assert_eq!(get(&cache, "a"), None);
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+}
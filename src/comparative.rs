@@ -0,0 +1,669 @@
+//! The comparative method: cognate sets, sound correspondences and proto-forms.
+//!
+//! Words for the same concept in related languages are grouped into cognate sets
+//! by how similar their sounds are. The members of each set are aligned phoneme by
+//! phoneme, and every aligned column is a sound correspondence between the languages.
+//! The proto-phoneme behind a column is the reflex most languages share, with ties
+//! broken by which sound could naturally have changed into the others.
+
+use crate::language::Language;
+use crate::phonology::PhonemeFeatures;
+use crate::sound_change::is_natural_change;
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Highest normalized distance at which two words are still considered cognate.
+const COGNATE_THRESHOLD: f32 = 0.45;
+
+/// Cost of aligning a phoneme against nothing.
+const GAP_COST: f32 = 1.0;
+
+/// A correspondence must occur this often to count as regular.
+const MIN_OCCURRENCES: usize = 2;
+
+/// Marks a lost phoneme in alignments and correspondences.
+const GAP: &str = "-";
+
+/// A comparative wordlist of several languages.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComparativeWordlist {
+    languages: Vec<String>,
+    entries: Vec<ComparisonEntry>,
+    correspondences: Vec<SoundCorrespondence>,
+}
+
+/// The words of all compared languages for one concept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComparisonEntry {
+    /// The concept compared
+    pub concept: String,
+    /// Each language's word, in the order of the languages
+    pub forms: Vec<String>,
+    /// The cognate sets the words fall into
+    pub cognate_sets: Vec<CognateSet>,
+}
+
+/// Words for one concept that descend from a common ancestor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CognateSet {
+    /// Label of the set within its concept ("A", "B", ...)
+    pub label: String,
+    /// Indices of the languages whose words belong to the set
+    pub members: Vec<usize>,
+    /// Reconstructed proto-form in asterisk notation, if the set has two or more members
+    pub proto_form: Option<String>,
+}
+
+/// A regular correspondence between the sounds of the compared languages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoundCorrespondence {
+    /// The reconstructed proto-phoneme ("-" if the column is an innovation)
+    pub proto: String,
+    /// The reflex in each language: `Some("-")` for a lost sound, `None` if the
+    /// language has no cognates in the sets the correspondence occurs in
+    pub reflexes: Vec<Option<String>>,
+    /// How many aligned columns show this correspondence
+    pub occurrences: usize,
+}
+
+impl ComparativeWordlist {
+    /// Compare the words of several languages for a list of concepts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{Language, CulturalProfile, Geography, DialectPoint, ComparativeWordlist};
+    ///
+    /// let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 3.0, 3.0);
+    /// let proto = Language::from_culture(culture, Geography::Plains, 7);
+    /// let north = proto.dialect(&DialectPoint::new("north", Geography::Mountains, 1.0));
+    /// let south = proto.dialect(&DialectPoint::new("south", Geography::Coastal, 1.0));
+    ///
+    /// let wordlist = ComparativeWordlist::new(&[&north, &south], &["water", "fire", "stone"]);
+    /// let water = &wordlist.entries()[0];
+    /// assert!(water.reconstruction().map_or(true, |form| form.starts_with('*')));
+    ///
+    /// let csv = wordlist.to_csv();
+    /// assert!(csv.starts_with("concept,language,form,cognate_set,proto_form"));
+    /// ```
+    pub fn new(languages: &[&Language], concepts: &[&str]) -> Self {
+        let mut entries = Vec::new();
+        let mut counts: BTreeMap<(Vec<Option<String>>, String), usize> = BTreeMap::new();
+
+        for concept in concepts {
+            let concept = concept.to_lowercase();
+            let forms: Vec<String> = languages
+                .iter()
+                .map(|language| language.translate_word(&concept))
+                .collect();
+            let segmented: Vec<Vec<&str>> = languages
+                .iter()
+                .zip(&forms)
+                .map(|(language, form)| language.genome.segment(form))
+                .collect();
+
+            let mut cognate_sets = Vec::new();
+            for (index, members) in cluster_cognates(&segmented).into_iter().enumerate() {
+                let mut proto_form = None;
+                if members.len() > 1 {
+                    let alignment = align(&members, &segmented);
+                    let mut proto = String::from("*");
+                    for column in &alignment {
+                        let reconstructed = reconstruct(column);
+                        if reconstructed != GAP {
+                            proto.push_str(reconstructed);
+                        }
+
+                        let mut reflexes = vec![None; languages.len()];
+                        for (member, segment) in members.iter().zip(column) {
+                            reflexes[*member] = Some(segment.unwrap_or(GAP).to_string());
+                        }
+                        *counts
+                            .entry((reflexes, reconstructed.to_string()))
+                            .or_insert(0) += 1;
+                    }
+                    proto_form = Some(proto);
+                }
+
+                cognate_sets.push(CognateSet {
+                    label: set_label(index),
+                    members,
+                    proto_form,
+                });
+            }
+
+            entries.push(ComparisonEntry {
+                concept,
+                forms,
+                cognate_sets,
+            });
+        }
+
+        let mut correspondences: Vec<SoundCorrespondence> = counts
+            .into_iter()
+            .filter(|(_, occurrences)| *occurrences >= MIN_OCCURRENCES)
+            .map(|((reflexes, proto), occurrences)| SoundCorrespondence {
+                proto,
+                reflexes,
+                occurrences,
+            })
+            .collect();
+        correspondences.sort_by_key(|c| core::cmp::Reverse(c.occurrences));
+
+        Self {
+            languages: languages.iter().map(|l| l.id.clone()).collect(),
+            entries,
+            correspondences,
+        }
+    }
+
+    /// IDs of the compared languages.
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// The comparison of each concept, in the order they were given.
+    pub fn entries(&self) -> &[ComparisonEntry] {
+        &self.entries
+    }
+
+    /// Regular sound correspondences, most frequent first.
+    pub fn correspondences(&self) -> &[SoundCorrespondence] {
+        &self.correspondences
+    }
+
+    /// Export the wordlist as CSV, one row per word.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("concept,language,form,cognate_set,proto_form\n");
+        for entry in &self.entries {
+            for set in &entry.cognate_sets {
+                for &member in &set.members {
+                    let row = [
+                        entry.concept.as_str(),
+                        self.languages[member].as_str(),
+                        entry.forms[member].as_str(),
+                        set.label.as_str(),
+                        set.proto_form.as_deref().unwrap_or(""),
+                    ];
+                    push_csv_row(&mut csv, &row);
+                }
+            }
+        }
+        csv
+    }
+
+    /// Export the sound correspondences as CSV, one row per correspondence.
+    pub fn correspondences_to_csv(&self) -> String {
+        let mut csv = String::new();
+        let header: Vec<&str> = ["proto"]
+            .into_iter()
+            .chain(self.languages.iter().map(String::as_str))
+            .chain(["occurrences"])
+            .collect();
+        push_csv_row(&mut csv, &header);
+
+        for correspondence in &self.correspondences {
+            let proto = format!("*{}", correspondence.proto);
+            let occurrences = correspondence.occurrences.to_string();
+            let row: Vec<&str> = [proto.as_str()]
+                .into_iter()
+                .chain(
+                    correspondence
+                        .reflexes
+                        .iter()
+                        .map(|r| r.as_deref().unwrap_or("")),
+                )
+                .chain([occurrences.as_str()])
+                .collect();
+            push_csv_row(&mut csv, &row);
+        }
+        csv
+    }
+
+    /// Export the cognate table and sound correspondences as Markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("## Cognates\n\n");
+        md.push_str(&markdown_row(
+            ["Concept"]
+                .into_iter()
+                .chain(self.languages.iter().map(String::as_str))
+                .chain(["Reconstruction"]),
+        ));
+        md.push_str(&markdown_separator(self.languages.len() + 2));
+
+        for entry in &self.entries {
+            let cells: Vec<String> = entry
+                .forms
+                .iter()
+                .enumerate()
+                .map(|(language, form)| match entry.cognate_set_of(language) {
+                    Some(set) => format!("{} ({})", form, set.label),
+                    None => form.clone(),
+                })
+                .collect();
+            md.push_str(&markdown_row(
+                [entry.concept.as_str()]
+                    .into_iter()
+                    .chain(cells.iter().map(String::as_str))
+                    .chain([entry.reconstruction().unwrap_or("")]),
+            ));
+        }
+
+        md.push_str("\n## Sound correspondences\n\n");
+        md.push_str(&markdown_row(
+            ["Proto"]
+                .into_iter()
+                .chain(self.languages.iter().map(String::as_str))
+                .chain(["Occurrences"]),
+        ));
+        md.push_str(&markdown_separator(self.languages.len() + 2));
+
+        for correspondence in &self.correspondences {
+            let proto = format!("*{}", correspondence.proto);
+            let occurrences = correspondence.occurrences.to_string();
+            md.push_str(&markdown_row(
+                [proto.as_str()]
+                    .into_iter()
+                    .chain(
+                        correspondence
+                            .reflexes
+                            .iter()
+                            .map(|r| r.as_deref().unwrap_or("")),
+                    )
+                    .chain([occurrences.as_str()]),
+            ));
+        }
+        md
+    }
+}
+
+impl ComparisonEntry {
+    /// Get the cognate set a language's word belongs to.
+    pub fn cognate_set_of(&self, language: usize) -> Option<&CognateSet> {
+        self.cognate_sets
+            .iter()
+            .find(|set| set.members.contains(&language))
+    }
+
+    /// The proto-form of the largest cognate set, if any set has two or more members.
+    pub fn reconstruction(&self) -> Option<&str> {
+        self.cognate_sets
+            .iter()
+            .filter(|set| set.proto_form.is_some())
+            .fold(None, |best: Option<&CognateSet>, set| match best {
+                Some(b) if b.members.len() >= set.members.len() => Some(b),
+                _ => Some(set),
+            })
+            .and_then(|set| set.proto_form.as_deref())
+    }
+}
+
+/// Cost of aligning two phonemes with each other.
+fn substitution_cost(a: &str, b: &str) -> f32 {
+    if a == b {
+        return 0.0;
+    }
+    match (PhonemeFeatures::of(a), PhonemeFeatures::of(b)) {
+        (Some(fa), Some(fb)) => {
+            let distance = fa.distance(&fb);
+            if distance.is_finite() {
+                (distance / 4.0).min(GAP_COST)
+            } else {
+                // Never align a consonant with a vowel if a gap will do
+                2.0 * GAP_COST
+            }
+        }
+        _ => GAP_COST,
+    }
+}
+
+/// Edit distance between two segmented words, normalized by the longer word.
+fn word_distance(a: &[&str], b: &[&str]) -> f32 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    let mut previous: Vec<f32> = (0..=b.len()).map(|j| j as f32 * GAP_COST).collect();
+    for (i, sa) in a.iter().enumerate() {
+        let mut current = vec![(i + 1) as f32 * GAP_COST];
+        for (j, sb) in b.iter().enumerate() {
+            let cost = (previous[j] + substitution_cost(sa, sb))
+                .min(previous[j + 1] + GAP_COST)
+                .min(current[j] + GAP_COST);
+            current.push(cost);
+        }
+        previous = current;
+    }
+
+    previous[b.len()] / longest as f32
+}
+
+/// Group languages into cognate sets by the similarity of their words.
+///
+/// Each word joins the existing set it is closest to on average, if that is
+/// within the cognate threshold, and otherwise starts a new set.
+fn cluster_cognates(words: &[Vec<&str>]) -> Vec<Vec<usize>> {
+    let mut sets: Vec<Vec<usize>> = Vec::new();
+
+    for (i, word) in words.iter().enumerate() {
+        let closest = sets
+            .iter()
+            .enumerate()
+            .map(|(s, members)| {
+                let total: f32 = members
+                    .iter()
+                    .map(|&m| word_distance(word, &words[m]))
+                    .sum();
+                (total / members.len() as f32, s)
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        match closest {
+            Some((distance, s)) if distance <= COGNATE_THRESHOLD => sets[s].push(i),
+            _ => sets.push(vec![i]),
+        }
+    }
+
+    sets
+}
+
+/// Align the words of a cognate set into columns of corresponding phonemes.
+///
+/// Words are added one at a time, starting from the most typical member, and each
+/// is aligned against the columns built so far. Column entries follow the order of
+/// `members`; `None` marks a word with no phoneme in that column.
+fn align<'a>(members: &[usize], words: &[Vec<&'a str>]) -> Vec<Vec<Option<&'a str>>> {
+    let total_distance = |m: usize| -> f32 {
+        members
+            .iter()
+            .map(|&o| word_distance(&words[m], &words[o]))
+            .sum()
+    };
+    let medoid = members
+        .iter()
+        .copied()
+        .min_by(|&a, &b| total_distance(a).total_cmp(&total_distance(b)))
+        .unwrap_or(members[0]);
+
+    let mut order: Vec<(f32, usize)> = members
+        .iter()
+        .enumerate()
+        .filter(|(_, &m)| m != medoid)
+        .map(|(position, &m)| (word_distance(&words[medoid], &words[m]), position))
+        .collect();
+    order.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let medoid_position = members.iter().position(|&m| m == medoid).unwrap_or(0);
+    let mut columns: Vec<Vec<(usize, &str)>> = words[medoid]
+        .iter()
+        .map(|segment| vec![(medoid_position, *segment)])
+        .collect();
+
+    for (_, position) in order {
+        columns = align_to_profile(columns, position, &words[members[position]]);
+    }
+
+    columns
+        .into_iter()
+        .map(|column| {
+            let mut entries = vec![None; members.len()];
+            for (position, segment) in column {
+                entries[position] = Some(segment);
+            }
+            entries
+        })
+        .collect()
+}
+
+/// Align one more word against existing alignment columns.
+fn align_to_profile<'a>(
+    columns: Vec<Vec<(usize, &'a str)>>,
+    position: usize,
+    word: &[&'a str],
+) -> Vec<Vec<(usize, &'a str)>> {
+    let column_cost = |column: &[(usize, &str)], segment: &str| -> f32 {
+        let total: f32 = column
+            .iter()
+            .map(|(_, s)| substitution_cost(s, segment))
+            .sum();
+        total / column.len() as f32
+    };
+
+    let (m, n) = (columns.len(), word.len());
+    let mut cost = vec![vec![0.0f32; n + 1]; m + 1];
+    for (i, row) in cost.iter_mut().enumerate() {
+        row[0] = i as f32 * GAP_COST;
+    }
+    for (j, cell) in cost[0].iter_mut().enumerate() {
+        *cell = j as f32 * GAP_COST;
+    }
+    for i in 1..=m {
+        for j in 1..=n {
+            cost[i][j] = (cost[i - 1][j - 1] + column_cost(&columns[i - 1], word[j - 1]))
+                .min(cost[i - 1][j] + GAP_COST)
+                .min(cost[i][j - 1] + GAP_COST);
+        }
+    }
+
+    // Trace back from the end, building the new columns in reverse
+    let mut columns: Vec<Option<Vec<(usize, &str)>>> = columns.into_iter().map(Some).collect();
+    let mut aligned = Vec::new();
+    let (mut i, mut j) = (m, n);
+    while i > 0 || j > 0 {
+        if i > 0
+            && j > 0
+            && cost[i][j]
+                == cost[i - 1][j - 1] + column_cost(columns[i - 1].as_ref().unwrap(), word[j - 1])
+        {
+            let mut column = columns[i - 1].take().unwrap();
+            column.push((position, word[j - 1]));
+            aligned.push(column);
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && cost[i][j] == cost[i - 1][j] + GAP_COST {
+            aligned.push(columns[i - 1].take().unwrap());
+            i -= 1;
+        } else {
+            aligned.push(vec![(position, word[j - 1])]);
+            j -= 1;
+        }
+    }
+
+    aligned.reverse();
+    aligned
+}
+
+/// Reconstruct the proto-phoneme behind an alignment column.
+///
+/// Each reflex scores one point per language showing it, plus half a point per
+/// language showing a sound it could naturally have changed into.
+fn reconstruct<'a>(column: &[Option<&'a str>]) -> &'a str {
+    let reflexes: Vec<&str> = column.iter().map(|s| s.unwrap_or(GAP)).collect();
+
+    let mut candidates: Vec<&str> = Vec::new();
+    for reflex in &reflexes {
+        if !candidates.contains(reflex) {
+            candidates.push(reflex);
+        }
+    }
+
+    let count = |reflex: &str| reflexes.iter().filter(|r| **r == reflex).count() as f32;
+    let score = |candidate: &str| -> f32 {
+        let descendants: f32 = candidates
+            .iter()
+            .filter(|other| {
+                let other = if **other == GAP { "" } else { **other };
+                candidate != GAP && is_natural_change(candidate, other)
+            })
+            .map(|other| count(other))
+            .sum();
+        count(candidate) + 0.5 * descendants
+    };
+
+    // The first candidate wins ties, so the result never depends on float noise
+    let mut best = candidates[0];
+    for candidate in &candidates[1..] {
+        if score(candidate) > score(best) {
+            best = candidate;
+        }
+    }
+
+    column
+        .iter()
+        .flatten()
+        .find(|s| **s == best)
+        .copied()
+        .unwrap_or(GAP)
+}
+
+/// Label the cognate set at `index`: "A" to "Z", then "A2", "B2", ...
+fn set_label(index: usize) -> String {
+    let letter = (b'A' + (index % 26) as u8) as char;
+    match index / 26 {
+        0 => letter.to_string(),
+        round => format!("{}{}", letter, round + 1),
+    }
+}
+
+/// Append a CSV row, quoting fields that need it.
+fn push_csv_row(csv: &mut String, fields: &[&str]) {
+    let quoted: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    csv.push_str(&quoted.join(","));
+    csv.push('\n');
+}
+
+/// Format a Markdown table row.
+fn markdown_row<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let cells: Vec<String> = cells.map(|cell| cell.replace('|', "\\|")).collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+/// Format the separator below a Markdown table header.
+fn markdown_separator(columns: usize) -> String {
+    format!("|{}\n", " --- |".repeat(columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::{CulturalProfile, Geography};
+    use crate::dialect::DialectPoint;
+
+    fn family() -> (Language, Vec<Language>) {
+        let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 3.0, 3.0);
+        let proto = Language::from_culture(culture, Geography::Plains, 2024);
+        let daughters = proto.dialect_continuum(&[
+            DialectPoint::new("north", Geography::Mountains, 1.5),
+            DialectPoint::new("east", Geography::Desert, 1.5),
+            DialectPoint::new("south", Geography::Coastal, 1.5),
+        ]);
+        (proto, daughters)
+    }
+
+    #[test]
+    fn test_word_distance() {
+        assert_eq!(word_distance(&["t", "a"], &["t", "a"]), 0.0);
+        let close = word_distance(&["p", "a", "t", "a"], &["p", "a", "d", "a"]);
+        let far = word_distance(&["p", "a", "t", "a"], &["m", "i", "r", "u"]);
+        assert!(close < COGNATE_THRESHOLD && far > COGNATE_THRESHOLD);
+    }
+
+    #[test]
+    fn test_reconstruction_prefers_majority_and_direction() {
+        assert_eq!(reconstruct(&[Some("p"), Some("p"), Some("f")]), "p");
+        // p > f is a natural change, f > p is not
+        assert_eq!(reconstruct(&[Some("f"), Some("p")]), "p");
+        assert_eq!(reconstruct(&[None, Some("h"), Some("h")]), "h");
+    }
+
+    #[test]
+    fn test_related_languages_share_cognates() {
+        let (_, daughters) = family();
+        let languages: Vec<&Language> = daughters.iter().collect();
+        let unrelated = Language::from_culture(
+            CulturalProfile::new(1.0, 5.0, 1.0, 5.0, 1.0, 5.0),
+            Geography::Forest,
+            999,
+        );
+
+        let concepts = &crate::lexicon::CORE_VOCABULARY[..40];
+        let wordlist = ComparativeWordlist::new(&languages, concepts);
+        let shared = wordlist
+            .entries()
+            .iter()
+            .filter(|entry| entry.cognate_sets.len() < entry.forms.len())
+            .count();
+        assert!(shared > 30, "only {} concepts with cognates", shared);
+        assert!(!wordlist.correspondences().is_empty());
+
+        let mixed = ComparativeWordlist::new(&[languages[0], &unrelated], concepts);
+        let chance = mixed
+            .entries()
+            .iter()
+            .filter(|entry| entry.cognate_sets.len() == 1)
+            .count();
+        assert!(chance < shared / 2, "{} chance cognates", chance);
+    }
+
+    #[test]
+    fn test_proto_forms_approach_ancestor() {
+        let (proto, daughters) = family();
+        let languages: Vec<&Language> = daughters.iter().collect();
+        let concepts = &crate::lexicon::CORE_VOCABULARY[..40];
+        let wordlist = ComparativeWordlist::new(&languages, concepts);
+
+        let mut exact = 0;
+        for entry in wordlist.entries() {
+            let reconstruction = entry.reconstruction().unwrap_or("");
+            if reconstruction.trim_start_matches('*') == proto.translate_word(&entry.concept) {
+                exact += 1;
+            }
+        }
+        assert!(exact > 20, "only {} proto-forms recovered", exact);
+    }
+
+    #[test]
+    fn test_exports() {
+        let (_, daughters) = family();
+        let languages: Vec<&Language> = daughters.iter().collect();
+        let wordlist = ComparativeWordlist::new(&languages, &["water", "fire"]);
+
+        let csv = wordlist.to_csv();
+        assert_eq!(csv.lines().count(), 1 + 2 * 3);
+        assert!(csv.contains(&format!("water,{},", daughters[0].id)));
+
+        let md = wordlist.to_markdown();
+        assert!(md.contains("| Concept | lang_2024_north | lang_2024_east | lang_2024_south |"));
+        assert!(md.contains("## Sound correspondences"));
+
+        assert_eq!(
+            push_csv_row_string(&["a,b", "c\"d"]),
+            "\"a,b\",\"c\"\"d\"\n"
+        );
+    }
+
+    fn push_csv_row_string(fields: &[&str]) -> String {
+        let mut csv = String::new();
+        push_csv_row(&mut csv, fields);
+        csv
+    }
+}
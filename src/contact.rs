@@ -0,0 +1,335 @@
+//! Language contact: borrowing words and sounds from other languages.
+//!
+//! Loanwords are adapted to the recipient language before they enter its lexicon.
+//! Each donor phoneme is replaced by the nearest sound the recipient has, and the
+//! result is repaired to fit the recipient's syllable patterns. The adapted word is
+//! stored with its provenance, so `Language::etymology` can report where it came from.
+
+use crate::genome::LinguisticGenome;
+use crate::language::Language;
+use crate::lexicon::{Etymology, LexicalEntry, LexicalShift, SemanticField};
+use crate::phonology::{is_vowel_symbol, PhonemeFeatures, Phonotactics};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// A word borrowed from another language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Loanword {
+    /// The concept the word means
+    pub concept: String,
+    /// ID of the donor language
+    pub donor: String,
+    /// The word in the donor language
+    pub source_form: String,
+    /// The word as adapted to the recipient language
+    pub form: String,
+}
+
+impl Language {
+    /// Borrow the words for some concepts from a donor language.
+    ///
+    /// Returns the loanwords in the order of `concepts`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{Language, CulturalProfile, Geography, Etymology};
+    ///
+    /// let traders = Language::from_culture(
+    ///     CulturalProfile::new(3.0, 4.0, 3.0, 4.0, 3.0, 3.0),
+    ///     Geography::Coastal,
+    ///     1,
+    /// );
+    /// let mut hill_folk = Language::from_culture(
+    ///     CulturalProfile::new(2.0, 2.0, 4.0, 2.0, 3.0, 2.0),
+    ///     Geography::Mountains,
+    ///     2,
+    /// );
+    ///
+    /// let loans = hill_folk.borrow_words(&traders, &["coin"]);
+    /// assert_eq!(hill_folk.translate_word("coin"), loans[0].form);
+    /// assert!(matches!(hill_folk.etymology("coin"), Etymology::Borrowed { .. }));
+    /// ```
+    pub fn borrow_words(&mut self, donor: &Language, concepts: &[&str]) -> Vec<Loanword> {
+        let stage = self.genome.sound_changes.len();
+
+        let loans: Vec<Loanword> = concepts
+            .iter()
+            .map(|concept| {
+                let concept = concept.to_lowercase();
+                let source_form = donor.translate_word(&concept);
+                let form = adapt_word(&self.genome, &donor.genome.segment(&source_form));
+                Loanword {
+                    concept,
+                    donor: donor.id.clone(),
+                    source_form,
+                    form,
+                }
+            })
+            .collect();
+
+        for loan in &loans {
+            self.genome.lexicon.insert(
+                &loan.concept,
+                LexicalEntry {
+                    form: loan.form.clone(),
+                    etymology: Etymology::Borrowed {
+                        donor: loan.donor.clone(),
+                        source_form: loan.source_form.clone(),
+                    },
+                    sound_change_stage: stage,
+                },
+            );
+        }
+
+        self.refresh();
+        loans
+    }
+
+    /// Borrow part of a semantic field from a donor language.
+    ///
+    /// `prestige` (0-1) is the donor's prestige in the eyes of this language's
+    /// speakers. Together with how readily the field is borrowed, it decides what
+    /// share of the field's concepts are borrowed. The selection is deterministic,
+    /// and a higher prestige borrows a superset of the concepts a lower one would.
+    pub fn borrow_semantic_field(
+        &mut self,
+        donor: &Language,
+        field: SemanticField,
+        prestige: f32,
+    ) -> Vec<Loanword> {
+        let rate = prestige.clamp(0.0, 1.0) * field.borrowability();
+        let selection = LexicalShift::new(self.contact_seed(donor), rate);
+
+        let concepts: Vec<&str> = field
+            .concepts()
+            .iter()
+            .copied()
+            .filter(|concept| selection.replaces(concept, self.genome.algorithm_version))
+            .collect();
+
+        self.borrow_words(donor, &concepts)
+    }
+
+    /// Adopt phonemes from a donor language.
+    ///
+    /// A share of the donor phonemes this language lacks, given by `prestige`
+    /// (0-1), become available for later loanwords. Native words keep using the
+    /// original inventory. Returns the newly adopted phonemes.
+    pub fn borrow_phonemes(&mut self, donor: &Language, prestige: f32) -> Vec<String> {
+        let inventory = &donor.genome.phoneme_inventory;
+        let mut missing: Vec<(u64, String)> = inventory
+            .all_consonants()
+            .into_iter()
+            .map(|c| c.0.as_str())
+            .chain(inventory.vowels.iter().map(|v| v.0.as_str()))
+            .filter(|p| !self.has_phoneme(p))
+            .map(|p| {
+                (
+                    self.genome.algorithm_version.hash(p, self.contact_seed(donor)),
+                    p.to_string(),
+                )
+            })
+            .collect();
+        missing.sort();
+
+        let count = libm::roundf(prestige.clamp(0.0, 1.0) * missing.len() as f32) as usize;
+        let adopted: Vec<String> = missing.into_iter().take(count).map(|(_, p)| p).collect();
+
+        self.genome.loan_phonemes.extend(adopted.iter().cloned());
+        self.refresh();
+        adopted
+    }
+
+    /// Check whether a phoneme is native or adopted in this language.
+    fn has_phoneme(&self, phoneme: &str) -> bool {
+        self.genome.phoneme_inventory.contains(phoneme)
+            || self.genome.loan_phonemes.iter().any(|p| p == phoneme)
+    }
+
+    /// Seed identifying contact between this language and a donor.
+    fn contact_seed(&self, donor: &Language) -> u64 {
+        self.genome
+            .algorithm_version
+            .hash(&format!("contact:{}", donor.id), self.genome.seed)
+    }
+}
+
+/// Adapt a foreign word, given as phonemes, to a language's sounds and syllables.
+pub(crate) fn adapt_word(recipient: &LinguisticGenome, segments: &[&str]) -> String {
+    let inventory = &recipient.phoneme_inventory;
+    let consonants: Vec<&str> = inventory
+        .all_consonants()
+        .into_iter()
+        .map(|c| c.0.as_str())
+        .chain(
+            recipient
+                .loan_phonemes
+                .iter()
+                .map(String::as_str)
+                .filter(|p| !is_vowel_symbol(p)),
+        )
+        .collect();
+    let vowels: Vec<&str> = inventory
+        .vowels
+        .iter()
+        .map(|v| v.0.as_str())
+        .chain(
+            recipient
+                .loan_phonemes
+                .iter()
+                .map(String::as_str)
+                .filter(|p| is_vowel_symbol(p)),
+        )
+        .collect();
+
+    let epenthetic = ["ə", "i", "e"]
+        .into_iter()
+        .find(|v| vowels.contains(v))
+        .or_else(|| vowels.first().copied())
+        .unwrap_or("a");
+    let prothetic = ["h", "ʔ", "j", "w"]
+        .into_iter()
+        .find(|c| consonants.contains(c))
+        .or_else(|| consonants.first().copied())
+        .unwrap_or("");
+
+    let mapped: Vec<&str> = segments
+        .iter()
+        .filter_map(|segment| {
+            let candidates = if is_vowel_symbol(segment) {
+                &vowels
+            } else {
+                &consonants
+            };
+            nearest_phoneme(segment, candidates)
+        })
+        .collect();
+
+    Phonotactics::from_patterns(&recipient.syllable_patterns)
+        .repair(&mapped, epenthetic, prothetic)
+        .concat()
+}
+
+/// Find the phoneme among `candidates` that sounds most like `phoneme`.
+fn nearest_phoneme<'a>(phoneme: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    if let Some(exact) = candidates.iter().find(|c| **c == phoneme) {
+        return Some(exact);
+    }
+
+    let Some(features) = PhonemeFeatures::of(phoneme) else {
+        return candidates.first().copied();
+    };
+
+    candidates
+        .iter()
+        .map(|candidate| {
+            let distance = PhonemeFeatures::of(candidate)
+                .map_or(f32::INFINITY, |other| features.distance(&other));
+            (distance, *candidate)
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::{CulturalProfile, Geography};
+
+    fn donor() -> Language {
+        let culture = CulturalProfile::new(3.0, 5.0, 3.0, 4.0, 3.0, 2.0);
+        Language::from_culture(culture, Geography::Desert, 31)
+    }
+
+    fn recipient() -> Language {
+        let culture = CulturalProfile::new(4.0, 1.0, 3.0, 3.0, 3.0, 2.0);
+        Language::from_culture(culture, Geography::Forest, 77)
+    }
+
+    #[test]
+    fn test_nearest_phoneme() {
+        assert_eq!(nearest_phoneme("ħ", &["p", "h", "m"]), Some("h"));
+        assert_eq!(nearest_phoneme("kʼ", &["k", "s"]), Some("k"));
+        assert_eq!(nearest_phoneme("o", &["a", "u"]), Some("u"));
+    }
+
+    #[test]
+    fn test_loanwords_fit_recipient() {
+        let donor = donor();
+        let mut recipient = recipient();
+        let loans = recipient.borrow_words(&donor, &["market", "temple", "sword"]);
+
+        for loan in &loans {
+            assert_eq!(recipient.translate_word(&loan.concept), loan.form);
+            for phoneme in recipient.genome.segment(&loan.form) {
+                assert!(
+                    recipient.genome.phoneme_inventory.contains(phoneme),
+                    "{} in {}",
+                    phoneme,
+                    loan.form
+                );
+            }
+            // Adapting an already adapted word changes nothing
+            let segments = recipient.genome.segment(&loan.form);
+            assert_eq!(adapt_word(&recipient.genome, &segments), loan.form);
+        }
+    }
+
+    #[test]
+    fn test_provenance() {
+        let donor = donor();
+        let mut recipient = recipient();
+        recipient.borrow_words(&donor, &["Coin"]);
+
+        assert_eq!(
+            recipient.etymology("coin"),
+            Etymology::Borrowed {
+                donor: donor.id.clone(),
+                source_form: donor.translate_word("coin"),
+            }
+        );
+        assert_eq!(recipient.etymology("tree"), Etymology::Native);
+    }
+
+    #[test]
+    fn test_prestige_weighted_fields() {
+        let donor = donor();
+
+        let mut low = recipient();
+        let mut high = recipient();
+        let low_loans = low.borrow_semantic_field(&donor, SemanticField::Trade, 0.2);
+        let high_loans = high.borrow_semantic_field(&donor, SemanticField::Trade, 0.9);
+
+        assert!(low_loans.len() <= high_loans.len());
+        for loan in &low_loans {
+            assert!(high_loans.iter().any(|l| l.concept == loan.concept));
+        }
+
+        // Basic vocabulary resists borrowing
+        let mut kin = recipient();
+        let kin_loans = kin.borrow_semantic_field(&donor, SemanticField::Kinship, 0.9);
+        assert!(kin_loans.len() <= high_loans.len());
+    }
+
+    #[test]
+    fn test_phonological_borrowing() {
+        let donor = donor();
+        let mut recipient = recipient();
+
+        let adopted = recipient.borrow_phonemes(&donor, 1.0);
+        assert!(adopted.iter().any(|p| p == "ħ"));
+
+        // Native words are unaffected, loanwords may now keep the new sounds
+        assert_eq!(
+            recipient.translate_word("tree"),
+            self::recipient().translate_word("tree")
+        );
+        let segments = ["ħ", "a"];
+        assert!(adapt_word(&recipient.genome, &segments).starts_with("ħ"));
+    }
+}
@@ -0,0 +1,311 @@
+//! Pidgins and creoles: new languages formed from two or more languages in contact.
+//!
+//! A creole takes most of its vocabulary from its lexifier, the dominant source
+//! language, while its grammar is simplified: a small inventory of widely shared,
+//! unmarked sounds, simple syllables, isolating morphology and SVO word order.
+
+use crate::contact::adapt_word;
+use crate::culture::CulturalProfile;
+use crate::error::Error;
+use crate::genome::{LinguisticGenome, MorphologyType, WordOrder};
+use crate::language::Language;
+use crate::lexicon::{Etymology, LexicalEntry, SemanticField, CORE_VOCABULARY};
+use crate::morphology::MorphemeType;
+use crate::phonology::{
+    Consonant, Manner, PhonemeCategory, PhonemeFeatures, PhonemeInventory, SyllableStructure, Vowel,
+};
+use alloc::{collections::BTreeSet, format, vec, vec::Vec};
+
+/// Vowels every creole keeps.
+const BASIC_VOWELS: &[&str] = &["a", "i", "u"];
+
+/// Vowels a creole keeps if enough of its sources share them.
+const MID_VOWELS: &[&str] = &["e", "o"];
+
+impl Language {
+    /// Generate a pidgin or creole from two or more languages.
+    ///
+    /// Each source comes with a dominance weight. The most dominant source is the
+    /// lexifier: the creole's core vocabulary, and the morphemes its names are built
+    /// from, are taken mostly from it and adapted to the creole's simplified sounds.
+    /// Every such word records its source language as its etymology.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sources` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{Language, CulturalProfile, Geography, Etymology, WordOrder};
+    ///
+    /// let colonists = Language::from_culture(
+    ///     CulturalProfile::new(2.0, 3.0, 4.0, 4.0, 2.0, 2.0),
+    ///     Geography::Plains,
+    ///     10,
+    /// );
+    /// let islanders = Language::from_culture(
+    ///     CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 4.0, 4.0),
+    ///     Geography::Coastal,
+    ///     20,
+    /// );
+    ///
+    /// let creole = Language::creole(&[(&colonists, 0.7), (&islanders, 0.3)], 99);
+    /// assert_eq!(creole.word_order(), WordOrder::SVO);
+    /// assert!(matches!(creole.etymology("water"), Etymology::Inherited { .. }));
+    /// ```
+    pub fn creole(sources: &[(&Language, f32)], seed: u64) -> Language {
+        Self::try_creole(sources, seed).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Generate a pidgin or creole, returning [`Error::NoSources`] instead of
+    /// panicking if `sources` is empty.
+    pub fn try_creole(sources: &[(&Language, f32)], seed: u64) -> Result<Language, Error> {
+        if sources.is_empty() {
+            return Err(Error::NoSources);
+        }
+
+        let weights = normalized_weights(sources);
+        let lexifier = weights
+            .iter()
+            .enumerate()
+            .fold(0, |best, (i, &w)| if w > weights[best] { i } else { best });
+
+        let culture = blend_cultures(sources, &weights);
+        let geography = sources[lexifier].0.geography();
+
+        let mut genome = LinguisticGenome::from_culture(culture, geography, seed);
+        genome.phoneme_inventory = simplified_inventory(sources, &weights);
+        genome.syllable_patterns = vec![
+            SyllableStructure::CV,
+            SyllableStructure::CVC,
+            SyllableStructure::V,
+        ];
+        genome.morphology_type = MorphologyType::Isolating;
+        genome.word_order = WordOrder::SVO;
+
+        // The lexifier dominates the vocabulary even more than the population
+        let vocabulary_weights: Vec<f32> = weights.iter().map(|w| w * w).collect();
+
+        for concept in creole_vocabulary() {
+            let version = genome.algorithm_version;
+            let mut rng = version.rng(version.hash(concept, seed));
+            let source = sources[rng.weighted_choice(&vocabulary_weights)?].0;
+
+            let source_form = source.translate_word(concept);
+            let form = adapt_word(&genome, &source.genome.segment(&source_form));
+            genome.lexicon.insert(
+                concept,
+                LexicalEntry {
+                    form,
+                    etymology: Etymology::Inherited {
+                        source: source.id.clone(),
+                        source_form,
+                    },
+                    sound_change_stage: 0,
+                },
+            );
+        }
+
+        let mut creole = Language::from_genome(genome, culture, geography);
+        creole.id = format!("creole_{}", seed);
+        Ok(creole)
+    }
+}
+
+/// Normalize source weights to sum to one, treating invalid weights as zero.
+fn normalized_weights(sources: &[(&Language, f32)]) -> Vec<f32> {
+    let weights: Vec<f32> = sources
+        .iter()
+        .map(|(_, w)| if w.is_finite() { w.max(0.0) } else { 0.0 })
+        .collect();
+    let total: f32 = weights.iter().sum();
+
+    if total > 0.0 {
+        weights.iter().map(|w| w / total).collect()
+    } else {
+        vec![1.0 / sources.len() as f32; sources.len()]
+    }
+}
+
+/// The weighted average of the source cultures.
+fn blend_cultures(sources: &[(&Language, f32)], weights: &[f32]) -> CulturalProfile {
+    let blend = |trait_of: fn(&CulturalProfile) -> f32| -> f32 {
+        sources
+            .iter()
+            .zip(weights)
+            .map(|((language, _), w)| trait_of(language.culture()) * w)
+            .sum()
+    };
+
+    CulturalProfile::new(
+        blend(|c| c.agreeableness),
+        blend(|c| c.openness),
+        blend(|c| c.conscientiousness),
+        blend(|c| c.extraversion),
+        blend(|c| c.honesty_humility),
+        blend(|c| c.emotionality),
+    )
+}
+
+/// Check whether a consonant is common enough across languages to survive in a creole.
+fn is_unmarked(phoneme: &str) -> bool {
+    match PhonemeFeatures::of(phoneme) {
+        Some(PhonemeFeatures::Consonant { manner, place, .. }) => {
+            manner != Manner::Ejective && !(5..=6).contains(&place)
+        }
+        _ => false,
+    }
+}
+
+/// Build an inventory of the unmarked sounds shared by most of the sources.
+fn simplified_inventory(sources: &[(&Language, f32)], weights: &[f32]) -> PhonemeInventory {
+    let share = |has: &dyn Fn(&PhonemeInventory) -> bool| -> f32 {
+        sources
+            .iter()
+            .zip(weights)
+            .filter(|((language, _), _)| has(&language.genome.phoneme_inventory))
+            .map(|(_, w)| w)
+            .sum()
+    };
+
+    let category = |category: PhonemeCategory, fallback: &[&str]| -> Vec<Consonant> {
+        let mut kept: Vec<Consonant> = Vec::new();
+        for (language, _) in sources {
+            for consonant in language.genome.phoneme_inventory.get_category(category) {
+                let widespread =
+                    share(&|inv| inv.get_category(category).contains(consonant)) >= 0.5;
+                if widespread && is_unmarked(&consonant.0) && !kept.contains(consonant) {
+                    kept.push(consonant.clone());
+                }
+            }
+        }
+        if kept.is_empty() {
+            kept = fallback.iter().map(|c| Consonant::new(c)).collect();
+        }
+        kept
+    };
+
+    let mut vowels: Vec<Vowel> = BASIC_VOWELS.iter().map(|v| Vowel::new(v)).collect();
+    for vowel in MID_VOWELS {
+        if share(&|inv| inv.vowels.iter().any(|v| v.0 == *vowel)) >= 0.5 {
+            vowels.push(Vowel::new(vowel));
+        }
+    }
+
+    let category_weights = (0..5)
+        .map(|i| {
+            sources
+                .iter()
+                .zip(weights)
+                .map(|((language, _), w)| {
+                    let inventory = &language.genome.phoneme_inventory;
+                    inventory.category_weights.get(i).copied().unwrap_or(0.0) * w
+                })
+                .sum()
+        })
+        .collect();
+
+    PhonemeInventory {
+        stops: category(PhonemeCategory::Stops, &["p", "t", "k"]),
+        fricatives: category(PhonemeCategory::Fricatives, &["s"]),
+        nasals: category(PhonemeCategory::Nasals, &["m", "n"]),
+        liquids: category(PhonemeCategory::Liquids, &["l"]),
+        glides: category(PhonemeCategory::Glides, &[]),
+        vowels,
+        category_weights,
+    }
+}
+
+/// The concepts a creole takes from its sources rather than generating itself.
+fn creole_vocabulary() -> BTreeSet<&'static str> {
+    CORE_VOCABULARY
+        .iter()
+        .copied()
+        .chain(MorphemeType::all().iter().map(|m| m.as_str()))
+        .chain(
+            SemanticField::all()
+                .iter()
+                .flat_map(|f| f.concepts().iter().copied()),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::Geography;
+
+    fn sources() -> (Language, Language) {
+        let colonists = Language::from_culture(
+            CulturalProfile::new(2.0, 4.5, 4.0, 4.0, 2.0, 2.0),
+            Geography::Mountains,
+            10,
+        );
+        let islanders = Language::from_culture(
+            CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 4.0, 4.0),
+            Geography::Coastal,
+            20,
+        );
+        (colonists, islanders)
+    }
+
+    #[test]
+    fn test_creole_grammar_is_simplified() {
+        let (colonists, islanders) = sources();
+        let creole = Language::creole(&[(&colonists, 0.6), (&islanders, 0.4)], 5);
+
+        assert_eq!(creole.word_order(), WordOrder::SVO);
+        assert_eq!(creole.genome.morphology_type, MorphologyType::Isolating);
+        assert!(creole.genome.syllable_patterns.len() <= 3);
+
+        let inventory = &creole.genome.phoneme_inventory;
+        assert!(!inventory.contains("kʼ"));
+        let lexifier_inventory = &colonists.genome.phoneme_inventory;
+        assert!(inventory.all_consonants().len() <= lexifier_inventory.all_consonants().len());
+    }
+
+    #[test]
+    fn test_vocabulary_comes_mostly_from_lexifier() {
+        let (colonists, islanders) = sources();
+        let creole = Language::creole(&[(&colonists, 0.75), (&islanders, 0.25)], 5);
+
+        let mut from_lexifier = 0;
+        let mut total = 0;
+        for concept in CORE_VOCABULARY {
+            match creole.etymology(concept) {
+                Etymology::Inherited {
+                    source,
+                    source_form,
+                } => {
+                    total += 1;
+                    if source == colonists.id {
+                        from_lexifier += 1;
+                        assert_eq!(source_form, colonists.translate_word(concept));
+                    }
+                }
+                other => panic!("unexpected etymology {:?}", other),
+            }
+        }
+        assert!(
+            from_lexifier * 10 > total * 7,
+            "{} of {}",
+            from_lexifier,
+            total
+        );
+    }
+
+    #[test]
+    fn test_creole_is_deterministic() {
+        let (colonists, islanders) = sources();
+        let c1 = Language::creole(&[(&colonists, 0.5), (&islanders, 0.5)], 8);
+        let c2 = Language::creole(&[(&colonists, 0.5), (&islanders, 0.5)], 8);
+
+        assert_eq!(c1.translate_word("fish"), c2.translate_word("fish"));
+        assert_eq!(c1.translate_word("harbor"), c2.translate_word("harbor"));
+        assert_eq!(
+            c1.naming.generate_simple_name(3),
+            c2.naming.generate_simple_name(3)
+        );
+    }
+}
@@ -1,8 +1,11 @@
 //! Cultural parameters that influence language generation.
 
+use crate::error::Error;
+
 /// HEXACO personality model scores (1-5 scale).
 /// These traits map to linguistic features.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CulturalProfile {
     /// Agreeableness: cooperation, empathy, patience.
     /// High → softer sounds (nasals, liquids), Low → harsher sounds (stops, clusters)
@@ -49,6 +52,58 @@ impl CulturalProfile {
         }
     }
 
+    /// Create a new cultural profile, rejecting scores outside the 1-5 scale.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{CulturalProfile, Error};
+    ///
+    /// assert!(CulturalProfile::try_new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0).is_ok());
+    /// assert!(matches!(
+    ///     CulturalProfile::try_new(4.0, f32::NAN, 2.0, 3.0, 3.0, 4.0),
+    ///     Err(Error::InvalidTrait { name: "openness", .. })
+    /// ));
+    /// ```
+    pub fn try_new(
+        agreeableness: f32,
+        openness: f32,
+        conscientiousness: f32,
+        extraversion: f32,
+        honesty_humility: f32,
+        emotionality: f32,
+    ) -> Result<Self, Error> {
+        let profile = Self::new(
+            agreeableness,
+            openness,
+            conscientiousness,
+            extraversion,
+            honesty_humility,
+            emotionality,
+        );
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// Check that every trait is a score between 1 and 5.
+    pub fn validate(&self) -> Result<(), Error> {
+        let traits = [
+            ("agreeableness", self.agreeableness),
+            ("openness", self.openness),
+            ("conscientiousness", self.conscientiousness),
+            ("extraversion", self.extraversion),
+            ("honesty_humility", self.honesty_humility),
+            ("emotionality", self.emotionality),
+        ];
+        for (name, value) in traits {
+            // Written so that NaN fails too
+            if !(1.0..=5.0).contains(&value) {
+                return Err(Error::InvalidTrait { name, value });
+            }
+        }
+        Ok(())
+    }
+
     /// Normalize a score to 0-1 range from 1-5 range.
     fn normalize(score: f32) -> f32 {
         (score - 1.0) / 4.0
@@ -74,6 +129,11 @@ impl CulturalProfile {
         Self::normalize(self.extraversion.clamp(1.0, 5.0))
     }
 
+    /// Get normalized honesty-humility (0-1).
+    pub fn normalized_honesty_humility(&self) -> f32 {
+        Self::normalize(self.honesty_humility.clamp(1.0, 5.0))
+    }
+
     /// Get normalized emotionality (0-1).
     pub fn normalized_emotionality(&self) -> f32 {
         Self::normalize(self.emotionality.clamp(1.0, 5.0))
@@ -82,6 +142,7 @@ impl CulturalProfile {
 
 /// Geographic environment that influences phonology.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Geography {
     /// Mountains: glottal stops, ejectives, shorter words, abrupt sounds
     Mountains,
@@ -108,6 +169,20 @@ mod tests {
         assert!((profile.normalized_agreeableness() - 0.0).abs() < 0.01);
         assert!((profile.normalized_openness() - 0.5).abs() < 0.01);
         assert!((profile.normalized_conscientiousness() - 1.0).abs() < 0.01);
+        assert!((profile.normalized_honesty_humility() - 0.75).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_try_new_validates_scores() {
+        assert!(CulturalProfile::try_new(1.0, 3.0, 5.0, 2.5, 4.0, 3.5).is_ok());
+
+        let invalid = [f32::NAN, f32::INFINITY, -2.0, 0.5, 5.5];
+        for value in invalid {
+            match CulturalProfile::try_new(3.0, 3.0, 3.0, 3.0, value, 3.0) {
+                Err(Error::InvalidTrait { name, .. }) => assert_eq!(name, "honesty_humility"),
+                other => panic!("accepted {}: {:?}", value, other),
+            }
+        }
     }
 }
 
@@ -0,0 +1,203 @@
+//! Dialect continua: regional varieties that diverge from a central language.
+//!
+//! Each dialect is derived from the base genome by adding sound changes, a wave of
+//! lexical replacement and, far enough from the center, a shift in morphology. The
+//! innovations depend only on the base language and the direction (geography) of a
+//! dialect point, while their extent grows with distance. Points further out in the
+//! same direction therefore share the innovations of nearer points and add more.
+
+use crate::culture::Geography;
+use crate::genome::{LinguisticGenome, MorphologyType};
+use crate::language::Language;
+use crate::lexicon::LexicalShift;
+use crate::sound_change::candidate_changes;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Sound changes accumulated by a maximally divergent dialect.
+const MAX_SOUND_CHANGES: f32 = 6.0;
+
+/// Fraction of the vocabulary replaced in a maximally divergent dialect.
+const MAX_REPLACEMENT_RATE: f32 = 0.35;
+
+/// A location in a dialect continuum.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DialectPoint {
+    /// Name of the dialect (used in its language ID)
+    pub name: String,
+    /// Local geography, which flavours the sound changes
+    pub geography: Geography,
+    /// Distance from the center of the language area (0 = the base language)
+    pub distance: f32,
+}
+
+impl DialectPoint {
+    /// Create a new dialect point.
+    pub fn new(name: &str, geography: Geography, distance: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            geography,
+            distance,
+        }
+    }
+
+    /// How far this dialect has diverged, from 0 (identical) approaching 1.
+    pub fn divergence(&self) -> f32 {
+        let distance = if self.distance.is_finite() {
+            self.distance.max(0.0)
+        } else {
+            0.0
+        };
+        distance / (1.0 + distance)
+    }
+}
+
+impl Language {
+    /// Generate a dialect of this language at a point of a dialect continuum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{Language, CulturalProfile, Geography, DialectPoint};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    /// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+    ///
+    /// let highland = language.dialect(&DialectPoint::new("highland", Geography::Mountains, 2.0));
+    /// assert_eq!(highland.id, "lang_12345_highland");
+    /// assert!(!highland.translate_word("house").is_empty());
+    /// ```
+    pub fn dialect(&self, point: &DialectPoint) -> Language {
+        let genome = dialect_genome(&self.genome, point);
+        let mut dialect = Language::from_genome(genome, *self.culture(), point.geography);
+        dialect.id = format!("{}_{}", self.id, point.name);
+        dialect.inherit_naming(self);
+        dialect
+    }
+
+    /// Generate one dialect for each point of a dialect continuum.
+    pub fn dialect_continuum(&self, points: &[DialectPoint]) -> Vec<Language> {
+        points.iter().map(|point| self.dialect(point)).collect()
+    }
+}
+
+/// Derive the genome of a dialect from its base genome.
+fn dialect_genome(base: &LinguisticGenome, point: &DialectPoint) -> LinguisticGenome {
+    let mut genome = base.clone();
+    let divergence = point.divergence();
+    if divergence <= 0.0 {
+        return genome;
+    }
+
+    // Shared by every dialect in the same direction from the center
+    let direction = format!("dialect:{:?}", point.geography);
+    let direction_seed = base.algorithm_version.hash(&direction, base.seed);
+    let mut rng = base.algorithm_version.rng(direction_seed);
+
+    let changes = candidate_changes(&base.phoneme_inventory, point.geography, &mut rng);
+    let count = (libm::roundf(divergence * MAX_SOUND_CHANGES) as usize).max(1);
+    genome.sound_changes.extend(changes.into_iter().take(count));
+
+    genome.lexical_shifts.push(LexicalShift::new(
+        direction_seed,
+        divergence * MAX_REPLACEMENT_RATE,
+    ));
+
+    // Only distant dialects restructure their morphology
+    let morphology_threshold = 0.5 + rng.next() as f32 * 0.4;
+    if divergence > morphology_threshold {
+        genome.morphology_type = match genome.morphology_type {
+            MorphologyType::Isolating => MorphologyType::Agglutinative,
+            MorphologyType::Agglutinative => MorphologyType::Fusional,
+            MorphologyType::Fusional => MorphologyType::Isolating,
+        };
+    }
+
+    genome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::CulturalProfile;
+
+    fn base_language() -> Language {
+        let culture = CulturalProfile::new(3.0, 4.0, 3.0, 3.0, 3.0, 3.0);
+        Language::from_culture(culture, Geography::Plains, 4242)
+    }
+
+    fn differing_words(a: &Language, b: &Language) -> usize {
+        let concepts = [
+            "water", "fire", "house", "tree", "stone", "bread", "river", "night", "mother",
+            "father", "hand", "eye", "sun", "moon", "road", "king", "horse", "salt", "iron",
+            "gold",
+        ];
+        concepts
+            .iter()
+            .filter(|c| a.translate_word(c) != b.translate_word(c))
+            .count()
+    }
+
+    #[test]
+    fn test_center_dialect_is_identical() {
+        let base = base_language();
+        let center = base.dialect(&DialectPoint::new("center", Geography::Plains, 0.0));
+
+        assert_eq!(differing_words(&base, &center), 0);
+        assert_eq!(center.word_order(), base.word_order());
+    }
+
+    #[test]
+    fn test_divergence_grows_with_distance() {
+        let base = base_language();
+        let points = [
+            DialectPoint::new("near", Geography::Mountains, 0.3),
+            DialectPoint::new("mid", Geography::Mountains, 1.5),
+            DialectPoint::new("far", Geography::Mountains, 8.0),
+        ];
+        let dialects = base.dialect_continuum(&points);
+
+        let near = differing_words(&base, &dialects[0]);
+        let far = differing_words(&base, &dialects[2]);
+        assert!(near <= far, "near {} far {}", near, far);
+        assert!(far > 0);
+
+        // Further dialects in the same direction keep the nearer innovations
+        let near_changes = &dialects[0].genome.sound_changes;
+        let far_changes = &dialects[2].genome.sound_changes;
+        assert!(far_changes.starts_with(near_changes));
+    }
+
+    #[test]
+    fn test_dialects_are_deterministic() {
+        let base = base_language();
+        let point = DialectPoint::new("coast", Geography::Coastal, 2.0);
+
+        let d1 = base.dialect(&point);
+        let d2 = base.dialect(&point);
+
+        assert_eq!(d1.translate_word("harbor"), d2.translate_word("harbor"));
+        assert_eq!(
+            d1.naming.generate_simple_name(7),
+            d2.naming.generate_simple_name(7)
+        );
+    }
+
+    #[test]
+    fn test_dialect_names_follow_sound_changes() {
+        let base = base_language();
+        let dialect = base.dialect(&DialectPoint::new("desert", Geography::Desert, 5.0));
+
+        let base_name = base.naming.generate_simple_name(1);
+        let dialect_name = dialect.naming.generate_simple_name(1);
+        let expected = dialect
+            .genome
+            .apply_sound_changes(&base_name.to_lowercase());
+
+        assert_eq!(dialect_name.to_lowercase(), expected);
+    }
+}
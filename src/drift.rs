@@ -0,0 +1,407 @@
+//! Language drift: how a language changes over simulated time.
+//!
+//! Drift combines three processes. Regular sound changes set in at irregular
+//! intervals, a steady share of the vocabulary is replaced every century, and now
+//! and then a frequent word wears down into a grammatical affix. Every event's time
+//! is a function of the drift seed and its own index, so the state of a language at
+//! any year is computed directly, without stepping through the years before it.
+
+use crate::culture::Geography;
+use crate::generation::generate_word;
+use crate::genome::{AlgorithmVersion, LinguisticGenome, MorphologyType, WordOrder};
+use crate::language::Language;
+use crate::lexicon::LexicalShift;
+use crate::phonology::is_vowel_symbol;
+use crate::sound_change::candidate_changes;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Average number of years between two sound changes.
+const SOUND_CHANGE_INTERVAL: u64 = 200;
+
+/// Average number of years between two grammaticalizations.
+const GRAMMATICALIZATION_INTERVAL: u64 = 500;
+
+/// Share of the vocabulary that survives a century.
+const RETENTION_PER_CENTURY: f32 = 0.98;
+
+/// A grammatical category that can be marked on a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GrammaticalFunction {
+    /// More than one (e.g., "houses")
+    Plural,
+    /// A particular, known thing (e.g., "the house")
+    Definite,
+    /// Some thing, not yet known (e.g., "a house")
+    Indefinite,
+    /// Negation (e.g., "not")
+    Negative,
+    /// Past tense (e.g., "walked")
+    Past,
+    /// Future tense (e.g., "will walk")
+    Future,
+    /// Location: in, at or on
+    Locative,
+    /// Smallness or affection (e.g., "booklet")
+    Diminutive,
+}
+
+impl GrammaticalFunction {
+    /// Get all grammatical functions.
+    pub fn all() -> &'static [GrammaticalFunction] {
+        &[
+            GrammaticalFunction::Plural,
+            GrammaticalFunction::Definite,
+            GrammaticalFunction::Indefinite,
+            GrammaticalFunction::Negative,
+            GrammaticalFunction::Past,
+            GrammaticalFunction::Future,
+            GrammaticalFunction::Locative,
+            GrammaticalFunction::Diminutive,
+        ]
+    }
+
+    /// The word that typically grammaticalizes into a marker of this function.
+    pub fn source_concept(&self) -> &'static str {
+        match self {
+            GrammaticalFunction::Plural => "many",
+            GrammaticalFunction::Definite => "that",
+            GrammaticalFunction::Indefinite => "one",
+            GrammaticalFunction::Negative => "not",
+            GrammaticalFunction::Past => "finish",
+            GrammaticalFunction::Future => "go",
+            GrammaticalFunction::Locative => "belly",
+            GrammaticalFunction::Diminutive => "child",
+        }
+    }
+}
+
+/// Where an affix attaches to its word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AffixPosition {
+    /// Before the word
+    Prefix,
+    /// After the word
+    Suffix,
+}
+
+impl AffixPosition {
+    /// Verb-initial languages tend to prefix, all others to suffix.
+    pub fn for_word_order(word_order: WordOrder) -> Self {
+        match word_order {
+            WordOrder::VSO | WordOrder::VOS => AffixPosition::Prefix,
+            _ => AffixPosition::Suffix,
+        }
+    }
+}
+
+/// A bound grammatical marker that developed from a free word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Affix {
+    /// What the affix marks
+    pub function: GrammaticalFunction,
+    /// The form of the affix when it developed
+    pub form: String,
+    /// Where it attaches
+    pub position: AffixPosition,
+    /// Number of the language's sound changes that predate the affix
+    pub sound_change_stage: usize,
+}
+
+impl Language {
+    /// The state of this language after `years` of drift.
+    ///
+    /// The same years and seed always give the same result, and the state at any
+    /// year is computed directly. Later snapshots with the same seed extend earlier
+    /// ones: they keep every sound change, replaced word and affix of the earlier
+    /// snapshot and add more. Evolving an evolved language with a new seed starts a
+    /// new era of drift on top of the old one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{Language, CulturalProfile, Geography};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    /// let old = Language::from_culture(culture, Geography::Coastal, 12345);
+    ///
+    /// let later = old.evolve(800, 1);
+    /// assert_eq!(later.id, "lang_12345_800y");
+    /// assert_eq!(later.translate_word("house"), old.evolve(800, 1).translate_word("house"));
+    /// ```
+    pub fn evolve(&self, years: u32, seed: u64) -> Language {
+        let genome = drift_genome(&self.genome, self.geography(), years as u64, seed);
+        let mut evolved = Language::from_genome(genome, *self.culture(), self.geography());
+        evolved.id = format!("{}_{}y", self.id, years);
+        evolved.inherit_naming(self);
+        evolved
+    }
+
+    /// Mark a word for a grammatical function.
+    ///
+    /// Uses the language's affix for the function if it has one. Otherwise the
+    /// function is expressed with a separate word, the one that would eventually
+    /// grammaticalize into an affix.
+    pub fn inflect(&self, concept: &str, function: GrammaticalFunction) -> String {
+        let word = self.translate_word(concept);
+
+        let (marker, position, bound) = match self.affix(function) {
+            Some(affix) => (
+                self.genome
+                    .apply_sound_changes_since(&affix.form, affix.sound_change_stage),
+                affix.position,
+                true,
+            ),
+            None => (
+                self.translate_word(function.source_concept()),
+                AffixPosition::for_word_order(self.genome.word_order),
+                false,
+            ),
+        };
+
+        let separator = if bound { "" } else { " " };
+        match position {
+            AffixPosition::Prefix => format!("{}{}{}", marker, separator, word),
+            AffixPosition::Suffix => format!("{}{}{}", word, separator, marker),
+        }
+    }
+
+    /// Get this language's affix for a grammatical function, if it has one.
+    pub fn affix(&self, function: GrammaticalFunction) -> Option<&Affix> {
+        self.genome.affixes.iter().find(|a| a.function == function)
+    }
+}
+
+/// Compute the genome of a language after `years` of drift.
+fn drift_genome(
+    base: &LinguisticGenome,
+    geography: Geography,
+    years: u64,
+    seed: u64,
+) -> LinguisticGenome {
+    let version = base.algorithm_version;
+    let drift_seed = version.hash("drift", base.seed ^ seed);
+    let mut genome = drift_lexicon(base, geography, years, drift_seed);
+
+    // Frequent words become affixes, in an order fixed by the drift seed
+    let mut rng = version.rng(version.hash("grammaticalization", drift_seed));
+    let mut functions: Vec<(f64, GrammaticalFunction)> = GrammaticalFunction::all()
+        .iter()
+        .map(|f| (rng.next(), *f))
+        .filter(|(_, f)| !base.affixes.iter().any(|a| a.function == *f))
+        .collect();
+    functions.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let count = event_count(
+        years,
+        GRAMMATICALIZATION_INTERVAL,
+        drift_seed,
+        "affix",
+        version,
+    );
+    for (k, (_, function)) in functions.into_iter().take(count).enumerate() {
+        let time = event_time(
+            k as u64,
+            GRAMMATICALIZATION_INTERVAL,
+            drift_seed,
+            "affix",
+            version,
+        );
+        let then = drift_lexicon(base, geography, time, drift_seed);
+        let word = generate_word(&then, function.source_concept());
+
+        genome.affixes.push(Affix {
+            function,
+            form: erode(&then, &word),
+            position: AffixPosition::for_word_order(genome.word_order),
+            sound_change_stage: then.sound_changes.len(),
+        });
+    }
+
+    // Enough affixes turn an isolating language agglutinative
+    let new_affixes = genome.affixes.len() - base.affixes.len();
+    if genome.morphology_type == MorphologyType::Isolating && new_affixes >= 2 {
+        genome.morphology_type = MorphologyType::Agglutinative;
+    }
+
+    genome
+}
+
+/// Apply the sound changes and lexical replacement of `years` of drift.
+fn drift_lexicon(
+    base: &LinguisticGenome,
+    geography: Geography,
+    years: u64,
+    drift_seed: u64,
+) -> LinguisticGenome {
+    let mut genome = base.clone();
+
+    let version = base.algorithm_version;
+    let mut rng = version.rng(drift_seed);
+    let count = event_count(years, SOUND_CHANGE_INTERVAL, drift_seed, "sound", version);
+    let changes = candidate_changes(&base.phoneme_inventory, geography, &mut rng)
+        .into_iter()
+        .filter(|change| !base.sound_changes.contains(change))
+        .take(count);
+    genome.sound_changes.extend(changes);
+
+    let rate = 1.0 - retention(years);
+    if rate > 0.0 {
+        genome
+            .lexical_shifts
+            .push(LexicalShift::new(drift_seed, rate));
+    }
+
+    genome
+}
+
+/// The year of the `k`th event of a kind, which happen every `interval` years on average.
+///
+/// Event `k` falls somewhere in the `k`th interval, so events are always in order.
+fn event_time(k: u64, interval: u64, seed: u64, kind: &str, version: AlgorithmVersion) -> u64 {
+    let mut rng = version.rng(version.hash(&format!("{}:{}", kind, k), seed));
+    k * interval + (rng.next() * interval as f64) as u64
+}
+
+/// Number of events of a kind that have happened by `years`.
+fn event_count(
+    years: u64,
+    interval: u64,
+    seed: u64,
+    kind: &str,
+    version: AlgorithmVersion,
+) -> usize {
+    // Every event in a completed interval has happened; the current one may have
+    let completed = years / interval;
+    let current = event_time(completed, interval, seed, kind, version) <= years;
+    completed as usize + current as usize
+}
+
+/// Share of the vocabulary that survives `years` of drift.
+fn retention(years: u64) -> f32 {
+    let centuries = years / 100;
+    let partial = (years % 100) as f32 / 100.0;
+
+    // Exponentiation by squaring over whole centuries
+    let mut result = 1.0f32;
+    let mut base = RETENTION_PER_CENTURY;
+    let mut exponent = centuries;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exponent >>= 1;
+    }
+
+    result * (1.0 - (1.0 - RETENTION_PER_CENTURY) * partial)
+}
+
+/// Wear a word down to an affix: everything up to and including its first vowel.
+pub(crate) fn erode(genome: &LinguisticGenome, word: &str) -> String {
+    let segments = genome.segment(word);
+    match segments.iter().position(|s| is_vowel_symbol(s)) {
+        Some(vowel) => segments[..=vowel].concat(),
+        None => word.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::CulturalProfile;
+    use crate::lexicon::{Etymology, CORE_VOCABULARY};
+
+    fn language() -> Language {
+        let culture = CulturalProfile::new(3.0, 3.0, 4.0, 2.0, 3.0, 3.0);
+        Language::from_culture(culture, Geography::RiverValley, 77)
+    }
+
+    #[test]
+    fn test_event_count_matches_replay() {
+        let seed = 1234;
+        let mut happened = 0;
+        let mut next = 0;
+        for year in 0..3000 {
+            while event_time(next, 200, seed, "sound", AlgorithmVersion::LATEST) <= year {
+                happened += 1;
+                next += 1;
+            }
+            assert_eq!(
+                event_count(year, 200, seed, "sound", AlgorithmVersion::LATEST),
+                happened
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_drift_at_year_zero() {
+        let base = language();
+        let same = base.evolve(0, 5);
+
+        for concept in &CORE_VOCABULARY[..30] {
+            assert_eq!(base.translate_word(concept), same.translate_word(concept));
+        }
+    }
+
+    #[test]
+    fn test_later_snapshots_extend_earlier_ones() {
+        let base = language();
+        let early = base.evolve(400, 9);
+        let late = base.evolve(2000, 9);
+
+        assert!(late
+            .genome
+            .sound_changes
+            .starts_with(&early.genome.sound_changes));
+        assert!(late.genome.affixes.starts_with(&early.genome.affixes));
+
+        let replaced = |l: &Language| -> Vec<&str> {
+            CORE_VOCABULARY
+                .iter()
+                .copied()
+                .filter(|c| l.etymology(c) == Etymology::Innovation)
+                .collect()
+        };
+        let early_replaced = replaced(&early);
+        let late_replaced = replaced(&late);
+        assert!(early_replaced.iter().all(|c| late_replaced.contains(c)));
+        assert!(late_replaced.len() > early_replaced.len());
+        assert!(late_replaced.len() < CORE_VOCABULARY.len() / 2);
+    }
+
+    #[test]
+    fn test_grammaticalization() {
+        let base = language();
+        assert_eq!(
+            base.inflect("house", GrammaticalFunction::Plural),
+            format!(
+                "{} {}",
+                base.translate_word("house"),
+                base.translate_word("many")
+            )
+        );
+
+        let later = base.evolve(4000, 3);
+        assert!(later.genome.affixes.len() >= 6);
+        for affix in &later.genome.affixes {
+            let inflected = later.inflect("house", affix.function);
+            assert!(!inflected.contains(' '));
+            assert!(inflected.contains(&later.translate_word("house")));
+        }
+    }
+
+    #[test]
+    fn test_retention() {
+        assert_eq!(retention(0), 1.0);
+        assert!((retention(100) - RETENTION_PER_CENTURY).abs() < 1e-6);
+        assert!((retention(1000) - RETENTION_PER_CENTURY.powi(10)).abs() < 1e-5);
+        assert!(retention(150) < retention(100) && retention(150) > retention(200));
+    }
+}
@@ -0,0 +1,66 @@
+//! The error type of the library.
+
+#[cfg(feature = "serde")]
+use crate::format::FormatError;
+use core::fmt;
+
+/// An error from building or using a language.
+#[derive(Debug)]
+pub enum Error {
+    /// A personality trait is not a number between 1 and 5
+    InvalidTrait {
+        /// Name of the trait
+        name: &'static str,
+        /// The rejected score
+        value: f32,
+    },
+    /// A genome cannot generate words
+    InvalidGenome(&'static str),
+    /// A random choice was asked to choose from nothing
+    EmptyChoice,
+    /// A creole was asked for without any source languages
+    NoSources,
+    /// No unused name was found within the allowed number of attempts
+    NamesExhausted {
+        /// Names tried before giving up
+        attempts: u32,
+    },
+    /// A language file could not be read or written
+    #[cfg(feature = "serde")]
+    Format(FormatError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidTrait { name, value } => {
+                write!(f, "{} must be a score between 1 and 5, got {}", name, value)
+            }
+            Error::InvalidGenome(reason) => write!(f, "invalid genome: {}", reason),
+            Error::EmptyChoice => write!(f, "cannot choose from an empty set"),
+            Error::NoSources => write!(f, "a creole needs at least one source language"),
+            Error::NamesExhausted { attempts } => {
+                write!(f, "no unused name found after {} attempts", attempts)
+            }
+            #[cfg(feature = "serde")]
+            Error::Format(error) => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "serde")]
+            Error::Format(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<FormatError> for Error {
+    fn from(error: FormatError) -> Self {
+        Error::Format(error)
+    }
+}
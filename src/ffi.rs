@@ -0,0 +1,646 @@
+//! C API, built under the `ffi` feature, for game engines and other native hosts.
+//!
+//! Languages and naming systems are handed out as opaque pointers that the caller
+//! frees with `phyla_language_free` and `phyla_naming_free`. Every function
+//! returns a [`PhylaStatus`] and writes its result through an out-pointer;
+//! strings written that way are owned by the caller and released with
+//! `phyla_string_free`. Panics are caught at the boundary and reported as
+//! `PHYLA_STATUS_PANIC`, so they never unwind into C.
+//!
+//! The header `include/phyla.h` is generated from this file by cbindgen.
+
+use crate::culture::{CulturalProfile, Geography};
+use crate::language::Language;
+use crate::naming::epithet::{Characteristic, EpithetContext};
+use crate::naming::personal::PersonalNameContext;
+use crate::naming::place::{PlaceNameContext, PlaceType};
+use crate::naming::NamingSystem;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// The result of a call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhylaStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer was null
+    NullPointer = 1,
+    /// A string was not valid UTF-8, or a result contained a NUL byte
+    InvalidString = 2,
+    /// A trait score, geography, place type or characteristic was out of range
+    InvalidArgument = 3,
+    /// A language file could not be read or written
+    InvalidJson = 4,
+    /// The library panicked; the handles passed in should not be used again
+    Panic = 5,
+}
+
+/// Geographies, as passed to `phyla_language_new`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhylaGeography {
+    Mountains = 0,
+    Coastal = 1,
+    Desert = 2,
+    Forest = 3,
+    Plains = 4,
+    RiverValley = 5,
+}
+
+/// Kinds of place, as passed to `phyla_naming_place_name`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhylaPlaceType {
+    Settlement = 0,
+    Natural = 1,
+    Landmark = 2,
+    Region = 3,
+}
+
+/// Characteristics, as passed to `phyla_naming_epithet`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhylaCharacteristic {
+    Tall = 0,
+    Short = 1,
+    Strong = 2,
+    Swift = 3,
+    Wise = 4,
+    Cunning = 5,
+    Mad = 6,
+    Honest = 7,
+    Brave = 8,
+    Cruel = 9,
+    Just = 10,
+    Silent = 11,
+    Loud = 12,
+    Beloved = 13,
+    Feared = 14,
+}
+
+/// Personality trait scores, each from 1 to 5.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PhylaCulture {
+    pub agreeableness: f32,
+    pub openness: f32,
+    pub conscientiousness: f32,
+    pub extraversion: f32,
+    pub honesty_humility: f32,
+    pub emotionality: f32,
+}
+
+/// An opaque handle to a language.
+pub struct PhylaLanguage(Language);
+
+/// An opaque handle to a naming system.
+pub struct PhylaNamingSystem(NamingSystem);
+
+/// Describe a status in English.
+///
+/// Takes the status as an integer so that any value from C is safe to pass. The
+/// returned string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn phyla_status_message(status: u32) -> *const c_char {
+    let message: &'static CStr = match status {
+        0 => c"ok",
+        1 => c"a required pointer was null",
+        2 => c"a string was not valid UTF-8 or contained a NUL byte",
+        3 => c"an argument was out of range",
+        4 => c"the language file could not be read or written",
+        5 => c"the library panicked",
+        _ => c"unknown status",
+    };
+    message.as_ptr()
+}
+
+/// Create a language from a culture, a geography and a seed.
+///
+/// # Safety
+///
+/// `culture` must point to a valid `PhylaCulture` and `out` to writable memory.
+/// The language written to `out` must be freed with `phyla_language_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_language_new(
+    culture: *const PhylaCulture,
+    geography: u32,
+    seed: u64,
+    out: *mut *mut PhylaLanguage,
+) -> PhylaStatus {
+    guard(|| {
+        let culture = culture.as_ref().ok_or(PhylaStatus::NullPointer)?;
+        let culture = CulturalProfile::try_new(
+            culture.agreeableness,
+            culture.openness,
+            culture.conscientiousness,
+            culture.extraversion,
+            culture.honesty_humility,
+            culture.emotionality,
+        )
+        .map_err(|_| PhylaStatus::InvalidArgument)?;
+        let geography = geography_from(geography).ok_or(PhylaStatus::InvalidArgument)?;
+        let language = Language::try_from_culture(culture, geography, seed)
+            .map_err(|_| PhylaStatus::InvalidArgument)?;
+        write_handle(out, PhylaLanguage(language))
+    })
+}
+
+/// Load a language saved with `phyla_language_to_json` or `Language::to_json`.
+///
+/// # Safety
+///
+/// `json` must be a NUL-terminated string and `out` must point to writable
+/// memory. The language written to `out` must be freed with `phyla_language_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_language_from_json(
+    json: *const c_char,
+    out: *mut *mut PhylaLanguage,
+) -> PhylaStatus {
+    guard(|| {
+        let json = read_str(json)?;
+        let language = Language::from_json(json).map_err(|_| PhylaStatus::InvalidJson)?;
+        write_handle(out, PhylaLanguage(language))
+    })
+}
+
+/// Save a language in the versioned language file format.
+///
+/// # Safety
+///
+/// `language` must be a live handle and `out` must point to writable memory.
+/// The string written to `out` must be freed with `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_language_to_json(
+    language: *const PhylaLanguage,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let language = &language.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        let json = language.to_json().map_err(|_| PhylaStatus::InvalidJson)?;
+        write_string(out, json)
+    })
+}
+
+/// Free a language. Passing null does nothing.
+///
+/// # Safety
+///
+/// `language` must be null or a handle from this library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_language_free(language: *mut PhylaLanguage) {
+    if !language.is_null() {
+        drop(Box::from_raw(language));
+    }
+}
+
+/// Write the language's ID.
+///
+/// # Safety
+///
+/// `language` must be a live handle and `out` must point to writable memory.
+/// The string written to `out` must be freed with `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_language_id(
+    language: *const PhylaLanguage,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let language = &language.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        write_string(out, language.id.clone())
+    })
+}
+
+/// Translate a concept to a word.
+///
+/// # Safety
+///
+/// `language` must be a live handle, `concept` a NUL-terminated string and `out`
+/// writable memory. The string written to `out` must be freed with
+/// `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_translate_word(
+    language: *const PhylaLanguage,
+    concept: *const c_char,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let language = &language.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        write_string(out, language.translate_word(read_str(concept)?))
+    })
+}
+
+/// Translate a phrase, applying the language's word order.
+///
+/// # Safety
+///
+/// `language` must be a live handle, `phrase` a NUL-terminated string and `out`
+/// writable memory. The string written to `out` must be freed with
+/// `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_translate_phrase(
+    language: *const PhylaLanguage,
+    phrase: *const c_char,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let language = &language.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        write_string(out, language.translate_phrase(read_str(phrase)?))
+    })
+}
+
+/// Copy a language's naming system into a handle of its own.
+///
+/// The naming system does not borrow the language, which may be freed first.
+///
+/// # Safety
+///
+/// `language` must be a live handle and `out` must point to writable memory.
+/// The naming system written to `out` must be freed with `phyla_naming_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_language_naming(
+    language: *const PhylaLanguage,
+    out: *mut *mut PhylaNamingSystem,
+) -> PhylaStatus {
+    guard(|| {
+        let language = &language.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        write_handle(out, PhylaNamingSystem(language.naming.clone()))
+    })
+}
+
+/// Free a naming system. Passing null does nothing.
+///
+/// # Safety
+///
+/// `naming` must be null or a handle from this library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_naming_free(naming: *mut PhylaNamingSystem) {
+    if !naming.is_null() {
+        drop(Box::from_raw(naming));
+    }
+}
+
+/// Generate a name from the language's sounds alone.
+///
+/// # Safety
+///
+/// `naming` must be a live handle and `out` must point to writable memory.
+/// The string written to `out` must be freed with `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_naming_simple_name(
+    naming: *const PhylaNamingSystem,
+    id: u64,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let naming = &naming.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        write_string(out, naming.generate_simple_name(id))
+    })
+}
+
+/// Generate a personal name. `parent` may be null; in patronymic cultures a
+/// parent's name becomes part of the name.
+///
+/// # Safety
+///
+/// `naming` must be a live handle, `parent` null or a NUL-terminated string, and
+/// `out` writable memory. The string written to `out` must be freed with
+/// `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_naming_personal_name(
+    naming: *const PhylaNamingSystem,
+    id: u64,
+    parent: *const c_char,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let naming = &naming.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        let context = if parent.is_null() {
+            PersonalNameContext::simple(id)
+        } else {
+            PersonalNameContext::with_parent(id, read_str(parent)?.to_string())
+        };
+        write_string(out, naming.generate_personal_name(&context))
+    })
+}
+
+/// Generate a name compounded from `count` meaningful morphemes.
+///
+/// # Safety
+///
+/// `naming` must be a live handle and `out` must point to writable memory.
+/// The string written to `out` must be freed with `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_naming_compound_name(
+    naming: *const PhylaNamingSystem,
+    id: u64,
+    count: usize,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let naming = &naming.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        write_string(out, naming.generate_compound_name(id, count))
+    })
+}
+
+/// Generate a place name for a `PhylaPlaceType`.
+///
+/// # Safety
+///
+/// `naming` must be a live handle and `out` must point to writable memory.
+/// The string written to `out` must be freed with `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_naming_place_name(
+    naming: *const PhylaNamingSystem,
+    id: u64,
+    place_type: u32,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let naming = &naming.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        let place_type = place_type_from(place_type).ok_or(PhylaStatus::InvalidArgument)?;
+        write_string(
+            out,
+            naming.generate_place_name(&PlaceNameContext::new(id, place_type)),
+        )
+    })
+}
+
+/// Generate an epithet for a `PhylaCharacteristic`.
+///
+/// Writes null to `out` when the culture does not use epithets.
+///
+/// # Safety
+///
+/// `naming` must be a live handle and `out` must point to writable memory.
+/// A string written to `out` must be freed with `phyla_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_naming_epithet(
+    naming: *const PhylaNamingSystem,
+    id: u64,
+    characteristic: u32,
+    out: *mut *mut c_char,
+) -> PhylaStatus {
+    guard(|| {
+        let naming = &naming.as_ref().ok_or(PhylaStatus::NullPointer)?.0;
+        let characteristic =
+            characteristic_from(characteristic).ok_or(PhylaStatus::InvalidArgument)?;
+        let context = EpithetContext::new(id).with_characteristic(characteristic);
+        match naming.generate_epithet(&context) {
+            Some(epithet) => write_string(out, epithet),
+            None => {
+                let out = out.as_mut().ok_or(PhylaStatus::NullPointer)?;
+                *out = ptr::null_mut();
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Free a string returned by this library. Passing null does nothing.
+///
+/// # Safety
+///
+/// `string` must be null or a string from this library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn phyla_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Run the body of an exported function, turning errors and panics into statuses.
+fn guard(body: impl FnOnce() -> Result<(), PhylaStatus>) -> PhylaStatus {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => PhylaStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => PhylaStatus::Panic,
+    }
+}
+
+/// Borrow a C string as UTF-8.
+unsafe fn read_str<'a>(string: *const c_char) -> Result<&'a str, PhylaStatus> {
+    if string.is_null() {
+        return Err(PhylaStatus::NullPointer);
+    }
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| PhylaStatus::InvalidString)
+}
+
+/// Hand a string to the caller.
+unsafe fn write_string(out: *mut *mut c_char, string: String) -> Result<(), PhylaStatus> {
+    let out = out.as_mut().ok_or(PhylaStatus::NullPointer)?;
+    *out = CString::new(string)
+        .map_err(|_| PhylaStatus::InvalidString)?
+        .into_raw();
+    Ok(())
+}
+
+/// Hand a boxed value to the caller.
+unsafe fn write_handle<T>(out: *mut *mut T, value: T) -> Result<(), PhylaStatus> {
+    let out = out.as_mut().ok_or(PhylaStatus::NullPointer)?;
+    *out = Box::into_raw(Box::new(value));
+    Ok(())
+}
+
+fn geography_from(value: u32) -> Option<Geography> {
+    let geography = match value {
+        0 => Geography::Mountains,
+        1 => Geography::Coastal,
+        2 => Geography::Desert,
+        3 => Geography::Forest,
+        4 => Geography::Plains,
+        5 => Geography::RiverValley,
+        _ => return None,
+    };
+    Some(geography)
+}
+
+fn place_type_from(value: u32) -> Option<PlaceType> {
+    let place_type = match value {
+        0 => PlaceType::Settlement,
+        1 => PlaceType::Natural,
+        2 => PlaceType::Landmark,
+        3 => PlaceType::Region,
+        _ => return None,
+    };
+    Some(place_type)
+}
+
+fn characteristic_from(value: u32) -> Option<Characteristic> {
+    const CHARACTERISTICS: [Characteristic; 15] = [
+        Characteristic::Tall,
+        Characteristic::Short,
+        Characteristic::Strong,
+        Characteristic::Swift,
+        Characteristic::Wise,
+        Characteristic::Cunning,
+        Characteristic::Mad,
+        Characteristic::Honest,
+        Characteristic::Brave,
+        Characteristic::Cruel,
+        Characteristic::Just,
+        Characteristic::Silent,
+        Characteristic::Loud,
+        Characteristic::Beloved,
+        Characteristic::Feared,
+    ];
+    CHARACTERISTICS.get(value as usize).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COASTAL: PhylaCulture = PhylaCulture {
+        agreeableness: 4.0,
+        openness: 3.0,
+        conscientiousness: 2.0,
+        extraversion: 3.0,
+        honesty_humility: 3.0,
+        emotionality: 4.0,
+    };
+
+    /// Take ownership of a string the library handed out.
+    unsafe fn take(string: *mut c_char) -> String {
+        let owned = CStr::from_ptr(string).to_str().unwrap().to_string();
+        phyla_string_free(string);
+        owned
+    }
+
+    unsafe fn new_language(culture: &PhylaCulture) -> *mut PhylaLanguage {
+        let mut language = ptr::null_mut();
+        let status = phyla_language_new(
+            culture,
+            PhylaGeography::Coastal as u32,
+            12345,
+            &mut language,
+        );
+        assert_eq!(status, PhylaStatus::Ok);
+        language
+    }
+
+    #[test]
+    fn test_matches_native_language() {
+        let native = Language::from_culture(
+            CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0),
+            Geography::Coastal,
+            12345,
+        );
+        unsafe {
+            let language = new_language(&COASTAL);
+            let mut out = ptr::null_mut();
+            assert_eq!(
+                phyla_translate_word(language, c"water".as_ptr(), &mut out),
+                PhylaStatus::Ok
+            );
+            assert_eq!(take(out), native.translate_word("water"));
+
+            let mut naming = ptr::null_mut();
+            assert_eq!(
+                phyla_language_naming(language, &mut naming),
+                PhylaStatus::Ok
+            );
+            phyla_language_free(language);
+
+            let status =
+                phyla_naming_place_name(naming, 3, PhylaPlaceType::Settlement as u32, &mut out);
+            assert_eq!(status, PhylaStatus::Ok);
+            let context = PlaceNameContext::new(3, PlaceType::Settlement);
+            assert_eq!(take(out), native.naming.generate_place_name(&context));
+            phyla_naming_free(naming);
+        }
+    }
+
+    #[test]
+    fn test_bad_arguments_return_statuses() {
+        unsafe {
+            let mut language = ptr::null_mut();
+            let invalid = PhylaCulture {
+                openness: 9.0,
+                ..COASTAL
+            };
+            assert_eq!(
+                phyla_language_new(&invalid, 0, 1, &mut language),
+                PhylaStatus::InvalidArgument
+            );
+            assert_eq!(
+                phyla_language_new(&COASTAL, 6, 1, &mut language),
+                PhylaStatus::InvalidArgument
+            );
+            assert_eq!(
+                phyla_language_new(ptr::null(), 0, 1, &mut language),
+                PhylaStatus::NullPointer
+            );
+            assert!(language.is_null());
+
+            let language = new_language(&COASTAL);
+            let mut out = ptr::null_mut();
+            let invalid_utf8 = [0xffu8, 0];
+            assert_eq!(
+                phyla_translate_word(language, invalid_utf8.as_ptr().cast(), &mut out),
+                PhylaStatus::InvalidString
+            );
+            assert_eq!(
+                phyla_translate_word(language, ptr::null(), &mut out),
+                PhylaStatus::NullPointer
+            );
+            assert_eq!(
+                phyla_language_from_json(c"{".as_ptr(), &mut ptr::null_mut()),
+                PhylaStatus::InvalidJson
+            );
+            phyla_language_free(language);
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        unsafe {
+            let language = new_language(&COASTAL);
+            let mut json = ptr::null_mut();
+            assert_eq!(phyla_language_to_json(language, &mut json), PhylaStatus::Ok);
+
+            let mut loaded = ptr::null_mut();
+            assert_eq!(phyla_language_from_json(json, &mut loaded), PhylaStatus::Ok);
+            phyla_string_free(json);
+
+            let mut a = ptr::null_mut();
+            let mut b = ptr::null_mut();
+            phyla_language_id(language, &mut a);
+            phyla_language_id(loaded, &mut b);
+            assert_eq!(take(a), take(b));
+
+            phyla_language_free(language);
+            phyla_language_free(loaded);
+        }
+    }
+
+    #[test]
+    fn test_panics_become_statuses() {
+        let status = guard(|| panic!("boom"));
+
+        assert_eq!(status, PhylaStatus::Panic);
+        let message = unsafe { CStr::from_ptr(phyla_status_message(status as u32)) };
+        assert_eq!(message.to_str().unwrap(), "the library panicked");
+    }
+
+    /// Rewrite the header after changing the C API with
+    /// `PHYLA_BLESS=1 cargo test --features ffi --lib test_header_is_current`.
+    #[test]
+    fn test_header_is_current() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/phyla.h"));
+
+        if std::env::var_os("PHYLA_BLESS").is_some() {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/include/phyla.h");
+            std::fs::write(path, generated).unwrap();
+            return;
+        }
+
+        assert!(
+            include_str!("../include/phyla.h") == generated,
+            "include/phyla.h is out of date with src/ffi.rs"
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! The versioned on-disk format for languages.
+//!
+//! A language file is a JSON document with a small header in front of the
+//! language itself:
+//!
+//! ```json
+//! { "format_version": 1, "algorithm_version": 1, "language": { ... } }
+//! ```
+//!
+//! The format version describes the layout of the file, and the algorithm version
+//! the generation algorithms the language's words and names come from. Both are
+//! checked before the language is read, so files from a newer library fail with a
+//! clear error instead of silently producing different words.
+
+use crate::genome::AlgorithmVersion;
+use crate::language::Language;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+/// The newest language file format this library reads and the one it writes.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// An error reading or writing a language file.
+#[derive(Debug)]
+pub enum FormatError {
+    /// The file uses a format version this library cannot read
+    UnsupportedFormatVersion {
+        /// Version found in the file
+        found: u32,
+        /// Newest version this library supports
+        supported: u32,
+    },
+    /// The language uses generation algorithms this library does not have
+    UnsupportedAlgorithmVersion {
+        /// Version found in the file
+        found: u32,
+        /// Newest version this library supports
+        latest: u32,
+    },
+    /// The file is not a valid language file, or the language could not be
+    /// written as one
+    Malformed(String),
+    /// The file could not be read or written
+    #[cfg(feature = "std")]
+    Io(io::Error),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::UnsupportedFormatVersion { found, supported } => write!(
+                f,
+                "language file format version {} is not supported (this library reads \
+                 versions 1 to {}); upgrade phyla-lang to a release that can migrate it",
+                found, supported
+            ),
+            FormatError::UnsupportedAlgorithmVersion { found, latest } => write!(
+                f,
+                "language uses generation algorithm version {}, but this library only has \
+                 versions 1 to {}; upgrade phyla-lang to load it with its original words",
+                found, latest
+            ),
+            FormatError::Malformed(reason) => write!(f, "malformed language file: {}", reason),
+            #[cfg(feature = "std")]
+            FormatError::Io(error) => write!(f, "could not access language file: {}", error),
+        }
+    }
+}
+
+impl core::error::Error for FormatError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            FormatError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for FormatError {
+    fn from(error: io::Error) -> Self {
+        FormatError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for FormatError {
+    fn from(error: serde_json::Error) -> Self {
+        #[cfg(feature = "std")]
+        if error.is_io() {
+            return FormatError::Io(error.into());
+        }
+        FormatError::Malformed(error.to_string())
+    }
+}
+
+/// A language file as written.
+#[derive(serde::Serialize)]
+struct LanguageFile<'a> {
+    format_version: u32,
+    algorithm_version: AlgorithmVersion,
+    language: &'a Language,
+}
+
+/// The header of a language file, read before anything else.
+#[derive(serde::Deserialize)]
+struct Header {
+    format_version: u32,
+    algorithm_version: u32,
+}
+
+impl Language {
+    /// Write this language in the versioned file format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{Language, CulturalProfile, Geography};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    /// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+    ///
+    /// let json = language.to_json().unwrap();
+    /// let loaded = Language::from_json(&json).unwrap();
+    /// assert_eq!(loaded.translate_word("house"), language.translate_word("house"));
+    /// ```
+    pub fn to_json(&self) -> Result<String, FormatError> {
+        Ok(serde_json::to_string_pretty(&self.file())?)
+    }
+
+    /// Read a language from the versioned file format.
+    pub fn from_json(json: &str) -> Result<Language, FormatError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        Self::from_value(value)
+    }
+
+    /// Write this language in the versioned file format.
+    ///
+    /// The writer is flushed, so a buffered writer reports its errors too.
+    #[cfg(feature = "std")]
+    pub fn save<W: io::Write>(&self, mut writer: W) -> Result<(), FormatError> {
+        serde_json::to_writer_pretty(&mut writer, &self.file())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a language in the versioned file format.
+    #[cfg(feature = "std")]
+    pub fn load<R: io::Read>(reader: R) -> Result<Language, FormatError> {
+        let value: serde_json::Value = serde_json::from_reader(reader)?;
+        Self::from_value(value)
+    }
+
+    /// This language with its file header.
+    fn file(&self) -> LanguageFile<'_> {
+        LanguageFile {
+            format_version: FORMAT_VERSION,
+            algorithm_version: self.genome.algorithm_version,
+            language: self,
+        }
+    }
+
+    /// Check the header of a parsed language file and read the language.
+    fn from_value(mut value: serde_json::Value) -> Result<Language, FormatError> {
+        let header = Header::deserialize_from(&value).ok_or_else(|| {
+            FormatError::Malformed("missing format_version or algorithm_version".to_string())
+        })?;
+
+        if header.format_version == 0 || header.format_version > FORMAT_VERSION {
+            return Err(FormatError::UnsupportedFormatVersion {
+                found: header.format_version,
+                supported: FORMAT_VERSION,
+            });
+        }
+        let algorithm_version = AlgorithmVersion::from_number(header.algorithm_version).ok_or(
+            FormatError::UnsupportedAlgorithmVersion {
+                found: header.algorithm_version,
+                latest: AlgorithmVersion::LATEST.number(),
+            },
+        )?;
+
+        let language = match value.get_mut("language") {
+            Some(language) => language.take(),
+            None => return Err(FormatError::Malformed("missing language".to_string())),
+        };
+        let language: Language = serde_json::from_value(language)?;
+
+        if language.genome.algorithm_version != algorithm_version {
+            return Err(FormatError::Malformed(format!(
+                "header says algorithm version {}, but the language uses {}",
+                algorithm_version.number(),
+                language.genome.algorithm_version.number()
+            )));
+        }
+        Ok(language)
+    }
+}
+
+impl Header {
+    /// Read the header fields of a language file, ignoring everything else.
+    fn deserialize_from(value: &serde_json::Value) -> Option<Header> {
+        serde::Deserialize::deserialize(value).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::{CulturalProfile, Geography};
+
+    fn language() -> Language {
+        let culture = CulturalProfile::new(3.0, 4.0, 2.0, 3.0, 3.0, 4.0);
+        Language::from_culture(culture, Geography::Forest, 321)
+    }
+
+    #[test]
+    fn test_header_is_written() {
+        let value: serde_json::Value =
+            serde_json::from_str(&language().to_json().unwrap()).unwrap();
+        assert_eq!(value["format_version"], FORMAT_VERSION);
+        assert_eq!(
+            value["algorithm_version"],
+            AlgorithmVersion::LATEST.number()
+        );
+        assert_eq!(
+            value["language"]["genome"]["algorithm_version"],
+            AlgorithmVersion::LATEST.number()
+        );
+    }
+
+    #[test]
+    fn test_unsupported_versions() {
+        let json = language().to_json().unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        value["format_version"] = 99.into();
+        let error = Language::from_json(&value.to_string()).err().unwrap();
+        assert!(matches!(
+            error,
+            FormatError::UnsupportedFormatVersion { found: 99, .. }
+        ));
+        assert!(error.to_string().contains("format version 99"));
+
+        value["format_version"] = FORMAT_VERSION.into();
+        value["algorithm_version"] = 42.into();
+        let error = Language::from_json(&value.to_string()).err().unwrap();
+        assert!(matches!(
+            error,
+            FormatError::UnsupportedAlgorithmVersion { found: 42, .. }
+        ));
+        assert!(error.to_string().contains("upgrade phyla-lang"));
+    }
+
+    #[test]
+    fn test_malformed_files() {
+        assert!(matches!(
+            Language::from_json("not json"),
+            Err(FormatError::Malformed(_))
+        ));
+        assert!(matches!(
+            Language::from_json("{\"language\": {}}"),
+            Err(FormatError::Malformed(_))
+        ));
+        assert!(matches!(
+            Language::from_json("{\"format_version\": 1, \"algorithm_version\": 1}"),
+            Err(FormatError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_save_reports_flush_errors() {
+        /// Accepts every write but fails to flush.
+        struct FailingFlush;
+
+        impl io::Write for FailingFlush {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Err(io::Error::other("disk full"))
+            }
+        }
+
+        assert!(matches!(
+            language().save(FailingFlush),
+            Err(FormatError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_unversioned_genome_is_v1() {
+        let json = language().to_json().unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["language"]["genome"]
+            .as_object_mut()
+            .unwrap()
+            .remove("algorithm_version");
+        value["algorithm_version"] = 1.into();
+
+        let loaded = Language::from_json(&value.to_string()).unwrap();
+        assert_eq!(loaded.genome.algorithm_version, AlgorithmVersion::V1);
+    }
+}
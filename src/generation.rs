@@ -1,13 +1,260 @@
 //! Word and phrase generation algorithms.
 
+use crate::error::Error;
 use crate::genome::LinguisticGenome;
+use crate::memory::HeapSize;
 use crate::phonology::PhonemeCategory;
-use crate::seeded_rng::{hash_deterministic, SeededRng};
+use crate::seeded_rng::SeededRng;
+use alloc::{collections::BTreeSet, string::String, sync::Arc, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::cell::RefCell;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+
+/// Consonant categories in the order of `PhonemeInventory::category_weights`.
+const CATEGORIES: [PhonemeCategory; 5] = [
+    PhonemeCategory::Stops,
+    PhonemeCategory::Fricatives,
+    PhonemeCategory::Nasals,
+    PhonemeCategory::Liquids,
+    PhonemeCategory::Glides,
+];
+
+/// The consonant categories a genome can draw from, with their weights.
+///
+/// Generating a consonant needs both; building them once per genome instead of
+/// once per consonant keeps bulk generation from allocating.
+#[derive(Debug, Clone)]
+pub(crate) struct SyllableTable {
+    categories: Vec<PhonemeCategory>,
+    weights: Vec<f32>,
+    /// Whether syllables can be filled: there are patterns, and vowels for the
+    /// vowel every pattern has
+    complete: bool,
+}
+
+impl SyllableTable {
+    pub(crate) fn new(genome: &LinguisticGenome) -> Self {
+        let inventory = &genome.phoneme_inventory;
+        let categories = inventory.available_categories();
+        let weights = categories
+            .iter()
+            .map(|&cat| {
+                let idx = CATEGORIES.iter().position(|&c| c == cat).unwrap_or(0);
+                inventory.category_weights[idx]
+            })
+            .collect();
+
+        Self {
+            categories,
+            weights,
+            complete: !genome.syllable_patterns.is_empty() && !inventory.vowels.is_empty(),
+        }
+    }
+
+    /// Whether the table was built from a genome with the same consonant
+    /// categories, weights and patterns as `genome`.
+    ///
+    /// Checking does not allocate, so a table can be checked before every use.
+    pub(crate) fn matches(&self, genome: &LinguisticGenome) -> bool {
+        let inventory = &genome.phoneme_inventory;
+        let mut table = self.categories.iter().zip(&self.weights);
+        let categories_match = CATEGORIES
+            .iter()
+            .enumerate()
+            .filter(|&(_, &category)| !inventory.get_category(category).is_empty())
+            .all(|(idx, &category)| {
+                table.next() == Some((&category, &inventory.category_weights[idx]))
+            });
+        categories_match
+            && table.next().is_none()
+            && self.complete
+                == (!genome.syllable_patterns.is_empty() && !inventory.vowels.is_empty())
+    }
+
+    /// Whether the genome can generate words at all.
+    pub(crate) fn complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Number of different syllables the genome can spell, at most.
+    ///
+    /// Different patterns can spell the same string, so this is an upper bound.
+    pub(crate) fn syllable_count(&self, genome: &LinguisticGenome) -> u64 {
+        if !self.complete {
+            return 0;
+        }
+        let inventory = &genome.phoneme_inventory;
+        let consonants: u64 = self
+            .categories
+            .iter()
+            .map(|&category| inventory.get_category(category).len() as u64)
+            .sum();
+        // Without consonants every 'C' is left empty
+        let consonants = consonants.max(1);
+        let vowels = inventory.vowels.len() as u64;
+
+        let patterns: BTreeSet<&str> = genome
+            .syllable_patterns
+            .iter()
+            .map(|p| p.pattern())
+            .collect();
+        patterns
+            .iter()
+            .map(|pattern| {
+                pattern.chars().fold(1u64, |count, slot| match slot {
+                    'C' => count.saturating_mul(consonants),
+                    'V' => count.saturating_mul(vowels),
+                    _ => count,
+                })
+            })
+            .fold(0, u64::saturating_add)
+    }
+}
+
+/// A [`SyllableTable`] built the first time it is needed.
+///
+/// The genome it was built from may be changed afterwards, so the table is
+/// checked against the genome on every use and replaced once if they no longer
+/// match. Without `std` this is a `RefCell`, so languages are not `Sync`.
+#[derive(Debug, Default)]
+pub(crate) struct LazySyllableTable {
+    #[cfg(feature = "std")]
+    cell: RwLock<Option<Arc<SyllableTable>>>,
+    #[cfg(not(feature = "std"))]
+    cell: RefCell<Option<Arc<SyllableTable>>>,
+}
+
+impl LazySyllableTable {
+    /// The table for `genome`, building it if there is none or the genome has
+    /// changed since it was built.
+    pub(crate) fn get(&self, genome: &LinguisticGenome) -> Arc<SyllableTable> {
+        match self.load() {
+            Some(table) if table.matches(genome) => table,
+            _ => {
+                let table = Arc::new(SyllableTable::new(genome));
+                self.store(Some(Arc::clone(&table)));
+                table
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn load(&self) -> Option<Arc<SyllableTable>> {
+        self.cell.read().unwrap().clone()
+    }
+
+    #[cfg(feature = "std")]
+    fn store(&self, table: Option<Arc<SyllableTable>>) {
+        *self.cell.write().unwrap() = table;
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn load(&self) -> Option<Arc<SyllableTable>> {
+        self.cell.borrow().clone()
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn store(&self, table: Option<Arc<SyllableTable>>) {
+        *self.cell.borrow_mut() = table;
+    }
+}
+
+impl Clone for LazySyllableTable {
+    fn clone(&self) -> Self {
+        let clone = Self::default();
+        clone.store(self.load());
+        clone
+    }
+}
+
+impl HeapSize for LazySyllableTable {
+    fn heap_size(&self) -> usize {
+        self.load().map_or(0, |table| {
+            core::mem::size_of::<SyllableTable>()
+                + table.categories.heap_size()
+                + table.weights.heap_size()
+        })
+    }
+}
+
+/// A short string formatted on the stack.
+pub(crate) struct StackStr<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> StackStr<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // Only whole `str`s are ever copied in
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+impl<const N: usize> fmt::Write for StackStr<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > N {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
 
 /// Generate a word for a given concept using deterministic generation.
 pub fn generate_word(genome: &LinguisticGenome, concept: &str) -> String {
-    let seed = hash_deterministic(concept, genome.seed);
-    let mut rng = SeededRng::new(seed);
+    generate_word_with(genome, &SyllableTable::new(genome), concept)
+}
+
+/// Generate the word for a concept with prebuilt tables.
+///
+/// A genome that cannot spell the word gives an empty one.
+pub(crate) fn generate_word_with(
+    genome: &LinguisticGenome,
+    table: &SyllableTable,
+    concept: &str,
+) -> String {
+    let mut word = String::new();
+    if generate_word_into(genome, table, concept, &mut word).is_err() {
+        word.clear();
+    }
+    word
+}
+
+/// Write the word for a concept to `out`.
+///
+/// Apart from what `out` does, only words changed by sound changes or taken
+/// from the lexicon allocate. Fails if `out` does, or if the genome cannot
+/// spell the word.
+pub(crate) fn generate_word_into<W: fmt::Write>(
+    genome: &LinguisticGenome,
+    table: &SyllableTable,
+    concept: &str,
+    out: &mut W,
+) -> fmt::Result {
+    if let Some(entry) = genome.lexicon.get(concept) {
+        return out
+            .write_str(&genome.apply_sound_changes_since(&entry.form, entry.sound_change_stage));
+    }
+
+    // A genome without syllable patterns or vowels has no words; the validating
+    // constructors reject such genomes
+    if !table.complete() {
+        return Ok(());
+    }
+
+    let seed = genome.concept_seed(concept);
+    let mut rng = genome.algorithm_version.rng(seed);
 
     // Determine syllable count based on concept length
     let syllable_count = if concept.len() < 4 {
@@ -16,70 +263,66 @@ pub fn generate_word(genome: &LinguisticGenome, concept: &str) -> String {
         2 + rng.range(0, 2)
     };
 
-    let mut word = String::new();
+    if genome.sound_changes.is_empty() {
+        for _ in 0..syllable_count {
+            write_syllable(genome, table, &mut rng, out)?;
+        }
+        return Ok(());
+    }
 
+    // Sound changes apply to the whole word
+    let mut word = String::new();
     for _ in 0..syllable_count {
-        let syllable = generate_syllable(genome, &mut rng);
-        word.push_str(&syllable);
+        write_syllable(genome, table, &mut rng, &mut word)?;
     }
-
-    word
+    out.write_str(&genome.apply_sound_changes(&word))
 }
 
-/// Generate a single syllable following the language's patterns.
-fn generate_syllable(genome: &LinguisticGenome, rng: &mut SeededRng) -> String {
-    let pattern = rng.choice(&genome.syllable_patterns);
-    let pattern_str = pattern.pattern();
+/// Write a single syllable following the language's patterns.
+///
+/// Fails if the writer does, or if the genome has nothing to choose from, which
+/// [`SyllableTable::complete`] rules out.
+pub(crate) fn write_syllable<W: fmt::Write>(
+    genome: &LinguisticGenome,
+    table: &SyllableTable,
+    rng: &mut SeededRng,
+    out: &mut W,
+) -> fmt::Result {
+    let pattern = rng
+        .choice(&genome.syllable_patterns)
+        .map_err(|_| fmt::Error)?;
 
-    let mut syllable = String::new();
-
-    for ch in pattern_str.chars() {
-        match ch {
-            'C' => {
-                let consonant = choose_consonant(genome, rng);
-                syllable.push_str(&consonant);
-            }
-            'V' => {
-                let vowel = rng.choice(&genome.phoneme_inventory.vowels);
-                syllable.push_str(&vowel.0);
-            }
-            _ => {}
-        }
+    for ch in pattern.pattern().chars() {
+        let phoneme = match ch {
+            'C' => choose_consonant(genome, table, rng),
+            'V' => rng
+                .choice(&genome.phoneme_inventory.vowels)
+                .map(|v| v.0.as_str()),
+            _ => continue,
+        };
+        out.write_str(phoneme.map_err(|_| fmt::Error)?)?;
     }
 
-    syllable
+    Ok(())
 }
 
 /// Choose a consonant based on weighted category probabilities.
-fn choose_consonant(genome: &LinguisticGenome, rng: &mut SeededRng) -> String {
-    let categories = genome.phoneme_inventory.available_categories();
-
-    if categories.is_empty() {
-        return String::new();
-    }
-
-    // Get weights for available categories
-    let weights: Vec<f32> = categories
-        .iter()
-        .map(|cat| {
-            let idx = match cat {
-                PhonemeCategory::Stops => 0,
-                PhonemeCategory::Fricatives => 1,
-                PhonemeCategory::Nasals => 2,
-                PhonemeCategory::Liquids => 3,
-                PhonemeCategory::Glides => 4,
-            };
-            genome.phoneme_inventory.category_weights[idx]
-        })
-        .collect();
+fn choose_consonant<'a>(
+    genome: &'a LinguisticGenome,
+    table: &SyllableTable,
+    rng: &mut SeededRng,
+) -> Result<&'a str, Error> {
+    if table.categories.is_empty() {
+        return Ok("");
+    }
 
-    let category_idx = rng.weighted_choice(&weights);
-    let category = categories[category_idx];
+    let category_idx = rng.weighted_choice(&table.weights)?;
+    let category = table.categories[category_idx];
 
     let consonants = genome.phoneme_inventory.get_category(category);
-    let consonant = rng.choice(consonants);
+    let consonant = rng.choice(consonants)?;
 
-    consonant.0.clone()
+    Ok(&consonant.0)
 }
 
 #[cfg(test)]
@@ -125,8 +368,24 @@ mod tests {
         let genome = LinguisticGenome::from_culture(culture, Geography::Plains, 12345);
         let mut rng = SeededRng::new(42);
 
-        let syllable = generate_syllable(&genome, &mut rng);
+        let mut syllable = String::new();
+        write_syllable(
+            &genome,
+            &SyllableTable::new(&genome),
+            &mut rng,
+            &mut syllable,
+        )
+        .unwrap();
         assert!(!syllable.is_empty());
     }
-}
 
+    #[test]
+    fn test_stack_str() {
+        use core::fmt::Write;
+
+        let mut s = StackStr::<8>::new();
+        write!(s, "name_{}", 42).unwrap();
+        assert_eq!(s.as_str(), "name_42");
+        assert!(write!(s, "{}", 123).is_err());
+    }
+}
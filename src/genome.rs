@@ -1,10 +1,17 @@
 //! The linguistic genome - the complete "DNA" of a language.
 
 use crate::culture::{CulturalProfile, Geography};
+use crate::drift::Affix;
+use crate::error::Error;
+use crate::lexicon::{LexicalShift, Lexicon};
 use crate::phonology::{Consonant, PhonemeInventory, ProsodicSystem, SyllableStructure, Vowel};
+use crate::seeded_rng::{HashKind, RngKind, SeededRng};
+use crate::sound_change::{apply_sound_changes, SoundChange};
+use alloc::{string::String, vec, vec::Vec};
 
 /// Word order patterns.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WordOrder {
     /// Subject-Verb-Object (English, Mandarin)
     SVO,
@@ -22,6 +29,7 @@ pub enum WordOrder {
 
 /// Morphological type of the language.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MorphologyType {
     /// Words are typically single morphemes (Chinese, Vietnamese)
     Isolating,
@@ -31,8 +39,114 @@ pub enum MorphologyType {
     Fusional,
 }
 
+/// Version of the generation algorithms a language uses.
+///
+/// Every version's algorithms are kept in the library, so a language generated
+/// under one version keeps producing the same words and names after upgrades.
+/// New languages use the latest version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AlgorithmVersion {
+    /// The original algorithms, with the JavaScript-compatible random generator
+    V1,
+    /// xoshiro256** random generator
+    V2,
+    /// xoshiro256** with concepts hashed by keyed SipHash-2-4
+    V3,
+    /// Version 3 with lineage names built from the language's kinship words
+    V4,
+    /// Version 4 with birth-order names built from the language's numerals
+    V5,
+}
+
+impl AlgorithmVersion {
+    /// The version used for new languages.
+    pub const LATEST: AlgorithmVersion = AlgorithmVersion::V5;
+
+    /// Get all supported versions, oldest first.
+    pub fn all() -> &'static [AlgorithmVersion] {
+        &[
+            AlgorithmVersion::V1,
+            AlgorithmVersion::V2,
+            AlgorithmVersion::V3,
+            AlgorithmVersion::V4,
+            AlgorithmVersion::V5,
+        ]
+    }
+
+    /// The version number, as stored in language files.
+    pub fn number(&self) -> u32 {
+        match self {
+            AlgorithmVersion::V1 => 1,
+            AlgorithmVersion::V2 => 2,
+            AlgorithmVersion::V3 => 3,
+            AlgorithmVersion::V4 => 4,
+            AlgorithmVersion::V5 => 5,
+        }
+    }
+
+    /// The random number generator this version uses.
+    pub fn rng_kind(&self) -> RngKind {
+        match self {
+            AlgorithmVersion::V1 => RngKind::JsCompatible,
+            AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5 => RngKind::Xoshiro256StarStar,
+        }
+    }
+
+    /// The hash this version uses to turn concepts into generation seeds.
+    pub fn hash_kind(&self) -> HashKind {
+        match self {
+            AlgorithmVersion::V1 | AlgorithmVersion::V2 => HashKind::Legacy,
+            AlgorithmVersion::V3 | AlgorithmVersion::V4 | AlgorithmVersion::V5 => {
+                HashKind::SipHash24
+            }
+        }
+    }
+
+    /// Hash a concept under a seed with this version's hash.
+    pub fn hash(&self, concept: &str, seed: u64) -> u64 {
+        self.hash_kind().hash(concept, seed)
+    }
+
+    /// Create this version's random number generator with a seed.
+    pub fn rng(&self, seed: u64) -> SeededRng {
+        SeededRng::with_kind(self.rng_kind(), seed)
+    }
+
+    /// Look up a version by number, if this library supports it.
+    pub fn from_number(number: u32) -> Option<Self> {
+        Self::all().iter().copied().find(|v| v.number() == number)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlgorithmVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.number())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AlgorithmVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let number = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+        Self::from_number(number).ok_or_else(|| {
+            serde::de::Error::custom(alloc::format!("unsupported algorithm version {}", number))
+        })
+    }
+}
+
+/// The version of genomes saved before versions were recorded.
+#[cfg(feature = "serde")]
+fn unversioned() -> AlgorithmVersion {
+    AlgorithmVersion::V1
+}
+
 /// The complete linguistic genome - all parameters needed to generate consistent output.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinguisticGenome {
     /// The phoneme inventory (available sounds)
     pub phoneme_inventory: PhonemeInventory,
@@ -51,14 +165,43 @@ pub struct LinguisticGenome {
 
     /// Generation seed for determinism
     pub seed: u64,
+
+    /// Version of the generation algorithms
+    #[cfg_attr(feature = "serde", serde(default = "unversioned"))]
+    pub algorithm_version: AlgorithmVersion,
+
+    /// Sound changes applied, in order, to every generated word
+    pub sound_changes: Vec<SoundChange>,
+
+    /// Waves of lexical replacement, oldest first
+    pub lexical_shifts: Vec<LexicalShift>,
+
+    /// Explicit words, such as loanwords, that override generation
+    pub lexicon: Lexicon,
+
+    /// Phonemes adopted from other languages, used only in loanwords
+    pub loan_phonemes: Vec<String>,
+
+    /// Grammatical affixes that developed from free words
+    pub affixes: Vec<Affix>,
 }
 
 impl LinguisticGenome {
     /// Generate a genome from cultural parameters and geography.
     pub fn from_culture(culture: CulturalProfile, geography: Geography, seed: u64) -> Self {
+        Self::from_culture_with_version(culture, geography, seed, AlgorithmVersion::LATEST)
+    }
+
+    /// Generate a genome using a specific version of the generation algorithms.
+    pub fn from_culture_with_version(
+        culture: CulturalProfile,
+        geography: Geography,
+        seed: u64,
+        algorithm_version: AlgorithmVersion,
+    ) -> Self {
         let phoneme_inventory = Self::generate_phoneme_inventory(&culture, &geography);
         let syllable_patterns = Self::generate_syllable_patterns(&culture, &geography);
-        let word_order = Self::determine_word_order(&culture, seed);
+        let word_order = Self::determine_word_order(&culture, seed, algorithm_version);
         let morphology_type = Self::determine_morphology(&culture);
 
         Self {
@@ -68,9 +211,74 @@ impl LinguisticGenome {
             morphology_type,
             word_order,
             seed,
+            algorithm_version,
+            sound_changes: Vec::new(),
+            lexical_shifts: Vec::new(),
+            lexicon: Lexicon::new(),
+            loan_phonemes: Vec::new(),
+            affixes: Vec::new(),
         }
     }
 
+    /// Check that this genome can generate words.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.syllable_patterns.is_empty() {
+            return Err(Error::InvalidGenome("no syllable patterns"));
+        }
+        if self.phoneme_inventory.vowels.is_empty() {
+            return Err(Error::InvalidGenome("no vowels"));
+        }
+        let weights = &self.phoneme_inventory.category_weights;
+        if weights.len() != 5 {
+            return Err(Error::InvalidGenome(
+                "consonant category weights must have one entry per category",
+            ));
+        }
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(Error::InvalidGenome(
+                "consonant category weights must be finite and non-negative",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the seed used to generate the word for a concept.
+    ///
+    /// This is the concept's native seed unless a lexical shift has replaced it,
+    /// in which case the most recent replacement wins.
+    pub fn concept_seed(&self, concept: &str) -> u64 {
+        self.lexical_shifts
+            .iter()
+            .rev()
+            .find(|shift| shift.replaces(concept, self.algorithm_version))
+            .map_or_else(
+                || self.algorithm_version.hash(concept, self.seed),
+                |shift| self.algorithm_version.hash(concept, self.seed ^ shift.seed),
+            )
+    }
+
+    /// Apply this language's sound changes to a word built from its inventory.
+    pub fn apply_sound_changes(&self, word: &str) -> String {
+        self.apply_sound_changes_since(word, 0)
+    }
+
+    /// Apply only the sound changes from index `stage` onwards to a word.
+    pub fn apply_sound_changes_since(&self, word: &str, stage: usize) -> String {
+        let changes = self.sound_changes.get(stage..).unwrap_or(&[]);
+        apply_sound_changes(word, changes, &self.phoneme_inventory, &self.loan_phonemes)
+    }
+
+    /// Split a word of this language into its phonemes.
+    pub fn segment<'a>(&self, word: &'a str) -> Vec<&'a str> {
+        let extra: Vec<&str> = self
+            .loan_phonemes
+            .iter()
+            .map(String::as_str)
+            .chain(self.sound_changes.iter().map(|c| c.to.as_str()))
+            .collect();
+        self.phoneme_inventory.segment_with(word, &extra)
+    }
+
     /// Generate phoneme inventory based on cultural traits and geography.
     fn generate_phoneme_inventory(
         culture: &CulturalProfile,
@@ -232,16 +440,18 @@ impl LinguisticGenome {
     }
 
     /// Determine word order based on culture.
-    fn determine_word_order(culture: &CulturalProfile, seed: u64) -> WordOrder {
-        use crate::seeded_rng::SeededRng;
-
+    fn determine_word_order(
+        culture: &CulturalProfile,
+        seed: u64,
+        algorithm_version: AlgorithmVersion,
+    ) -> WordOrder {
         let conscientiousness = culture.normalized_conscientiousness();
 
         // High conscientiousness tends toward SOV (structured, verb at end)
         // Low conscientiousness tends toward VSO or VOS (verb-initial)
         // Medium tends toward SVO (most common cross-linguistically)
 
-        let mut rng = SeededRng::new(seed.wrapping_mul(7919));
+        let mut rng = algorithm_version.rng(seed.wrapping_mul(7919));
 
         if conscientiousness > 0.7 {
             // Prefer SOV
@@ -325,5 +535,19 @@ mod tests {
             mountain.phoneme_inventory.stops.len()
         );
     }
-}
 
+    #[test]
+    fn test_validate() {
+        let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 3.0, 3.0);
+        let genome = LinguisticGenome::from_culture(culture, Geography::Plains, 7);
+        assert!(genome.validate().is_ok());
+
+        let mut broken = genome.clone();
+        broken.syllable_patterns.clear();
+        assert!(matches!(broken.validate(), Err(Error::InvalidGenome(_))));
+
+        let mut broken = genome;
+        broken.phoneme_inventory.category_weights[2] = f32::NAN;
+        assert!(matches!(broken.validate(), Err(Error::InvalidGenome(_))));
+    }
+}
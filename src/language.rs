@@ -1,11 +1,16 @@
 //! The main Language struct and its public API.
 
+#[cfg(feature = "std")]
+use crate::cache::{CacheConfig, CacheStats, WordCache};
 use crate::culture::{CulturalProfile, Geography};
-use crate::generation::generate_word;
-use crate::genome::{LinguisticGenome, WordOrder};
+use crate::error::Error;
+use crate::generation::{generate_word_into, LazySyllableTable};
+use crate::genome::{AlgorithmVersion, LinguisticGenome, WordOrder};
+use crate::lexicon::Etymology;
+use crate::memory::{HeapSize, MemoryUsage};
 use crate::naming::NamingSystem;
-use std::collections::HashMap;
-use std::sync::Mutex;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
 
 /// A complete language with its genome and optional caching.
 pub struct Language {
@@ -13,6 +18,9 @@ pub struct Language {
     pub id: String,
 
     /// The linguistic genome (complete language specification)
+    ///
+    /// Words already cached keep their old form after the genome is changed;
+    /// call [`clear_cache`](Self::clear_cache) to regenerate them.
     pub genome: LinguisticGenome,
 
     /// Cultural profile used to generate this language
@@ -24,8 +32,12 @@ pub struct Language {
     /// Naming system for generating names
     pub naming: NamingSystem,
 
-    /// Optional cache for frequently-used words
-    lexicon_cache: Mutex<HashMap<String, String>>,
+    /// Bounded cache of translated words
+    #[cfg(feature = "std")]
+    lexicon_cache: WordCache,
+
+    /// Consonant tables for the genome, built on first use
+    table: LazySyllableTable,
 }
 
 impl Language {
@@ -46,7 +58,20 @@ impl Language {
     /// let language = Language::from_culture(culture, Geography::Coastal, 12345);
     /// ```
     pub fn from_culture(culture: CulturalProfile, geography: Geography, seed: u64) -> Self {
-        let genome = LinguisticGenome::from_culture(culture, geography, seed);
+        Self::from_culture_with_version(culture, geography, seed, AlgorithmVersion::LATEST)
+    }
+
+    /// Create a new language using a specific version of the generation algorithms.
+    ///
+    /// Use [`AlgorithmVersion::V1`] to reproduce the JavaScript implementation.
+    pub fn from_culture_with_version(
+        culture: CulturalProfile,
+        geography: Geography,
+        seed: u64,
+        algorithm_version: AlgorithmVersion,
+    ) -> Self {
+        let genome =
+            LinguisticGenome::from_culture_with_version(culture, geography, seed, algorithm_version);
         let naming = NamingSystem::new(genome.clone(), culture, geography);
         let id = format!("lang_{}", seed);
 
@@ -56,10 +81,60 @@ impl Language {
             culture,
             geography,
             naming,
-            lexicon_cache: Mutex::new(HashMap::new()),
+            #[cfg(feature = "std")]
+            lexicon_cache: WordCache::new(CacheConfig::default()),
+            table: LazySyllableTable::default(),
         }
     }
 
+    /// Create a new language, returning an error if the cultural profile has a
+    /// score outside the 1-5 scale.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{Language, CulturalProfile, Geography};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 9.0, 4.0);
+    /// assert!(Language::try_from_culture(culture, Geography::Coastal, 12345).is_err());
+    /// ```
+    pub fn try_from_culture(
+        culture: CulturalProfile,
+        geography: Geography,
+        seed: u64,
+    ) -> Result<Self, Error> {
+        Self::try_from_culture_with_version(culture, geography, seed, AlgorithmVersion::LATEST)
+    }
+
+    /// Create a new language using a specific version of the generation
+    /// algorithms, returning an error if the cultural profile is invalid.
+    pub fn try_from_culture_with_version(
+        culture: CulturalProfile,
+        geography: Geography,
+        seed: u64,
+        algorithm_version: AlgorithmVersion,
+    ) -> Result<Self, Error> {
+        culture.validate()?;
+        Ok(Self::from_culture_with_version(
+            culture,
+            geography,
+            seed,
+            algorithm_version,
+        ))
+    }
+
+    /// Create a language from a genome, returning an error if the genome cannot
+    /// generate words or the cultural profile is invalid.
+    pub fn try_from_genome(
+        genome: LinguisticGenome,
+        culture: CulturalProfile,
+        geography: Geography,
+    ) -> Result<Self, Error> {
+        genome.validate()?;
+        culture.validate()?;
+        Ok(Self::from_genome(genome, culture, geography))
+    }
+
     /// Create a language directly from a genome.
     ///
     /// Note: This requires providing culture and geography for the naming system.
@@ -77,7 +152,9 @@ impl Language {
             culture,
             geography,
             naming,
-            lexicon_cache: Mutex::new(HashMap::new()),
+            #[cfg(feature = "std")]
+            lexicon_cache: WordCache::new(CacheConfig::default()),
+            table: LazySyllableTable::default(),
         }
     }
 
@@ -96,26 +173,83 @@ impl Language {
     /// assert_eq!(word, language.translate_word("house"));
     /// ```
     pub fn translate_word(&self, concept: &str) -> String {
-        let concept = concept.to_lowercase();
+        let mut word = String::new();
+        // Only a genome that cannot spell the word fails
+        if self.translate_word_into(concept, &mut word).is_err() {
+            word.clear();
+        }
+        word
+    }
+
+    /// Write the word for a concept to `out`, the same word
+    /// [`translate_word`](Self::translate_word) returns.
+    ///
+    /// With a reused buffer, cached words and words generated while caching is
+    /// disabled are translated without allocating. Fails if `out` does, or if
+    /// the genome cannot spell the word.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{CulturalProfile, Geography, Language};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    /// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+    ///
+    /// let mut phrase = String::new();
+    /// for concept in ["big", "river"] {
+    ///     if !phrase.is_empty() {
+    ///         phrase.push(' ');
+    ///     }
+    ///     language.translate_word_into(concept, &mut phrase).unwrap();
+    /// }
+    /// ```
+    pub fn translate_word_into<W: fmt::Write>(&self, concept: &str, out: &mut W) -> fmt::Result {
+        // Only copy the concept if lowercasing changes it
+        let lowercase;
+        let concept = if concept.chars().flat_map(char::to_lowercase).eq(concept.chars()) {
+            concept
+        } else {
+            lowercase = concept.to_lowercase();
+            &lowercase
+        };
 
         // Check cache first
-        {
-            let cache = self.lexicon_cache.lock().unwrap();
-            if let Some(cached) = cache.get(&concept) {
-                return cached.clone();
-            }
+        #[cfg(feature = "std")]
+        if let Some(written) = self.lexicon_cache.get_into(concept, out) {
+            return written;
         }
 
-        // Generate word
-        let word = generate_word(&self.genome, &concept);
+        // Generate the whole word to cache it
+        #[cfg(feature = "std")]
+        if self.lexicon_cache.is_enabled() {
+            let mut word = String::new();
+            generate_word_into(&self.genome, &self.table.get(&self.genome), concept, &mut word)?;
+            self.lexicon_cache.insert(concept, &word);
+            return out.write_str(&word);
+        }
 
-        // Cache it
-        {
-            let mut cache = self.lexicon_cache.lock().unwrap();
-            cache.insert(concept, word.clone());
+        generate_word_into(&self.genome, &self.table.get(&self.genome), concept, out)
+    }
+
+    /// Get the etymology of this language's word for a concept.
+    pub fn etymology(&self, concept: &str) -> Etymology {
+        let concept = concept.to_lowercase();
+
+        if let Some(entry) = self.genome.lexicon.get(&concept) {
+            return entry.etymology.clone();
         }
 
-        word
+        if self
+            .genome
+            .lexical_shifts
+            .iter()
+            .any(|shift| shift.replaces(&concept, self.genome.algorithm_version))
+        {
+            Etymology::Innovation
+        } else {
+            Etymology::Native
+        }
     }
 
     /// Translate a phrase to this language.
@@ -203,16 +337,166 @@ impl Language {
         self.genome.word_order
     }
 
+    /// Get the cultural profile this language was generated from.
+    pub fn culture(&self) -> &CulturalProfile {
+        &self.culture
+    }
+
+    /// Get the geography this language was generated for.
+    pub fn geography(&self) -> Geography {
+        self.geography
+    }
+
+    /// Rebuild everything derived from the genome after it has changed.
+    ///
+    /// The naming configuration is kept, so overrides made when the language was
+    /// built survive.
+    pub(crate) fn refresh(&mut self) {
+        let previous = core::mem::replace(
+            &mut self.naming,
+            NamingSystem::new(self.genome.clone(), self.culture, self.geography),
+        );
+        self.naming.pattern = previous.pattern;
+        self.naming.combining_rule = previous.combining_rule;
+        self.naming.syllables_per_name = previous.syllables_per_name;
+        self.naming.surname_inheritance = previous.surname_inheritance;
+        self.naming.gender_marking = previous.gender_marking;
+        self.naming.birth_order_naming = previous.birth_order_naming;
+        self.table = LazySyllableTable::default();
+        #[cfg(feature = "std")]
+        self.clear_cache();
+    }
+
+    /// Adopt the naming conventions of the language this one descends from.
+    ///
+    /// Name length is left alone, since it depends on geography.
+    pub(crate) fn inherit_naming(&mut self, parent: &Language) {
+        self.naming.pattern = parent.naming.pattern;
+        self.naming.combining_rule = parent.naming.combining_rule;
+        self.naming.surname_inheritance = parent.naming.surname_inheritance;
+        self.naming.gender_marking = parent.naming.gender_marking;
+        self.naming.birth_order_naming = parent.naming.birth_order_naming;
+    }
+
     /// Clear the lexicon cache.
+    #[cfg(feature = "std")]
     pub fn clear_cache(&self) {
-        let mut cache = self.lexicon_cache.lock().unwrap();
-        cache.clear();
+        self.lexicon_cache.clear();
     }
 
     /// Get the number of cached words.
+    #[cfg(feature = "std")]
     pub fn cache_size(&self) -> usize {
-        let cache = self.lexicon_cache.lock().unwrap();
-        cache.len()
+        self.lexicon_cache.len()
+    }
+
+    /// Get the cache's hit, miss and eviction counts.
+    #[cfg(feature = "std")]
+    pub fn cache_stats(&self) -> CacheStats {
+        self.lexicon_cache.stats()
+    }
+
+    /// Get the cache's settings.
+    #[cfg(feature = "std")]
+    pub fn cache_config(&self) -> CacheConfig {
+        self.lexicon_cache.config()
+    }
+
+    /// Replace the cache with an empty one using new settings.
+    ///
+    /// Use [`CacheConfig::disabled`] to turn caching off.
+    #[cfg(feature = "std")]
+    pub fn set_cache_config(&mut self, config: CacheConfig) {
+        self.lexicon_cache = WordCache::new(config);
+    }
+
+    /// Estimate how many bytes this language uses.
+    ///
+    /// The word cache grows as words are translated, up to its capacity; use
+    /// `CacheConfig` to bound it.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            language: core::mem::size_of::<Self>() + self.id.heap_size() + self.table.heap_size(),
+            genome: self.genome.heap_size(),
+            naming: self.naming.heap_size(),
+            #[cfg(feature = "std")]
+            cache: self.lexicon_cache.heap_size(),
+            #[cfg(not(feature = "std"))]
+            cache: 0,
+        }
+    }
+}
+
+/// The serialized form of a [`Language`].
+///
+/// The naming system is stored as its configuration; its morphemes are generated
+/// again from the genome when the language is loaded. The word cache is not stored.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LanguageData {
+    id: String,
+    genome: LinguisticGenome,
+    culture: CulturalProfile,
+    geography: Geography,
+    naming: NamingConfig,
+}
+
+/// The parts of a naming system that are not derived from its genome.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct NamingConfig {
+    pattern: crate::naming::NamePattern,
+    combining_rule: crate::morphology::CombiningRule,
+    syllables_per_name: usize,
+    // Languages saved by older releases lack these and use the culture's
+    #[serde(default)]
+    surname_inheritance: Option<crate::naming::surname::SurnameInheritance>,
+    #[serde(default)]
+    gender_marking: Option<crate::naming::gender::GenderMarking>,
+    #[serde(default)]
+    birth_order_naming: Option<crate::naming::birth_order::BirthOrderNaming>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Language {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        LanguageData {
+            id: self.id.clone(),
+            genome: self.genome.clone(),
+            culture: self.culture,
+            geography: self.geography,
+            naming: NamingConfig {
+                pattern: self.naming.pattern,
+                combining_rule: self.naming.combining_rule,
+                syllables_per_name: self.naming.syllables_per_name,
+                surname_inheritance: Some(self.naming.surname_inheritance),
+                gender_marking: Some(self.naming.gender_marking),
+                birth_order_naming: Some(self.naming.birth_order_naming),
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Language {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = LanguageData::deserialize(deserializer)?;
+        let mut language = Language::from_genome(data.genome, data.culture, data.geography);
+        language.id = data.id;
+        language.naming.pattern = data.naming.pattern;
+        language.naming.combining_rule = data.naming.combining_rule;
+        language.naming.syllables_per_name = data.naming.syllables_per_name;
+        if let Some(inheritance) = data.naming.surname_inheritance {
+            language.naming.surname_inheritance = inheritance;
+        }
+        if let Some(marking) = data.naming.gender_marking {
+            language.naming.gender_marking = marking;
+        }
+        if let Some(birth_order_naming) = data.naming.birth_order_naming {
+            language.naming.birth_order_naming = birth_order_naming;
+        }
+        Ok(language)
     }
 }
 
@@ -250,6 +534,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_cache() {
         let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
         let language = Language::from_culture(culture, Geography::Coastal, 12345);
@@ -269,6 +554,102 @@ mod tests {
         assert_eq!(language.cache_size(), 0);
     }
 
+    #[test]
+    fn test_translate_word_into() {
+        let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+        let language = Language::from_culture(culture, Geography::Coastal, 12345);
+
+        let mut out = String::from(">");
+        language.translate_word_into("House", &mut out).unwrap();
+        language.translate_word_into("house", &mut out).unwrap();
+        let word = language.translate_word("house");
+        assert_eq!(out, format!(">{}{}", word, word));
+    }
+
+    #[test]
+    fn test_changed_genome() {
+        let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+        let mut language = Language::from_culture(culture, Geography::Coastal, 12345);
+        let concepts = ["house", "river", "stone", "fire", "water", "tree"];
+        let before: Vec<String> = concepts
+            .iter()
+            .map(|c| language.translate_word(c))
+            .collect();
+        let name = language.naming.generate_simple_name(7);
+
+        language.genome.phoneme_inventory.stops.clear();
+        language.naming.genome.phoneme_inventory.stops.clear();
+        #[cfg(feature = "std")]
+        language.clear_cache();
+
+        let after: Vec<String> = concepts
+            .iter()
+            .map(|c| language.translate_word(c))
+            .collect();
+        let expected: Vec<String> = concepts
+            .iter()
+            .map(|c| crate::generation::generate_word(&language.genome, c))
+            .collect();
+        assert_eq!(after, expected);
+        assert_ne!(after, before);
+
+        let fresh = NamingSystem::new(language.naming.genome.clone(), culture, Geography::Coastal);
+        assert_eq!(
+            language.naming.generate_simple_name(7),
+            fresh.generate_simple_name(7)
+        );
+        assert_ne!(language.naming.generate_simple_name(7), name);
+    }
+
+    #[test]
+    fn test_changed_genome_rebuilds_table_once() {
+        use alloc::sync::Arc;
+
+        let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+        let mut language = Language::from_culture(culture, Geography::Coastal, 12345);
+        language.translate_word("house");
+        language.naming.generate_simple_name(7);
+        let old = language.table.get(&language.genome);
+        let old_naming = language.naming.table();
+
+        language.genome.phoneme_inventory.stops.clear();
+        language.naming.genome.phoneme_inventory.stops.clear();
+        language.translate_word("house");
+        language.naming.generate_simple_name(7);
+
+        let table = language.table.get(&language.genome);
+        assert!(!Arc::ptr_eq(&table, &old));
+        language.translate_word("river");
+        assert!(Arc::ptr_eq(&table, &language.table.get(&language.genome)));
+
+        let naming = language.naming.table();
+        assert!(!Arc::ptr_eq(&naming, &old_naming));
+        language.naming.generate_simple_name(8);
+        assert!(Arc::ptr_eq(&naming, &language.naming.table()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_cache_config() {
+        let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+        let mut language = Language::from_culture(culture, Geography::Coastal, 12345);
+        let cached = language.translate_word("house");
+        language.translate_word("house");
+        assert_eq!(language.cache_stats().hits, 1);
+
+        language.set_cache_config(CacheConfig::disabled());
+        assert_eq!(language.translate_word("house"), cached);
+        assert_eq!(language.cache_size(), 0);
+        assert_eq!(language.cache_stats(), CacheStats::default());
+
+        language.set_cache_config(CacheConfig::new(3));
+        for concept in ["house", "tree", "river", "stone", "fire"] {
+            language.translate_word(concept);
+        }
+        assert!(language.cache_size() <= 3);
+        assert_eq!(language.translate_word("house"), cached);
+    }
+
     #[test]
     fn test_different_languages() {
         let culture1 = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
@@ -283,5 +664,32 @@ mod tests {
         // Different languages should produce different words
         assert_ne!(word1, word2);
     }
-}
 
+    #[test]
+    fn test_fallible_constructors() {
+        let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+        let language = Language::try_from_culture(culture, Geography::Coastal, 12345).unwrap();
+        assert_eq!(
+            language.translate_word("house"),
+            Language::from_culture(culture, Geography::Coastal, 12345).translate_word("house")
+        );
+
+        let invalid = CulturalProfile::new(4.0, -1.0, 2.0, 3.0, 3.0, 4.0);
+        assert!(matches!(
+            Language::try_from_culture(invalid, Geography::Coastal, 12345),
+            Err(Error::InvalidTrait { name: "openness", .. })
+        ));
+
+        let mut genome = language.genome.clone();
+        genome.phoneme_inventory.vowels.clear();
+        assert!(matches!(
+            Language::try_from_genome(genome.clone(), culture, Geography::Coastal),
+            Err(Error::InvalidGenome(_))
+        ));
+
+        // Generating from an unvalidated broken genome gives empty words, not a panic
+        let broken = Language::from_genome(genome, culture, Geography::Coastal);
+        assert_eq!(broken.translate_word("house"), "");
+        assert_eq!(broken.naming.generate_simple_name(1), "");
+    }
+}
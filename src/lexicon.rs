@@ -0,0 +1,266 @@
+//! Lexical change: which word a language uses for each concept.
+
+use crate::genome::AlgorithmVersion;
+use crate::memory::{btree_map_size, HeapSize};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+/// Where a language's word for a concept comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Etymology {
+    /// Generated from the language's own phonology
+    Native,
+    /// A native word that replaced an older one through lexical change
+    Innovation,
+    /// Borrowed from another language and adapted to this one
+    Borrowed {
+        /// ID of the donor language
+        donor: String,
+        /// The word's form in the donor language
+        source_form: String,
+    },
+    /// Inherited from a language this one was formed from
+    Inherited {
+        /// ID of the source language
+        source: String,
+        /// The word's form in the source language
+        source_form: String,
+    },
+}
+
+/// A word stored explicitly in a language's lexicon rather than generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LexicalEntry {
+    /// The form of the word when it entered the language
+    pub form: String,
+    /// Where the word comes from
+    pub etymology: Etymology,
+    /// Number of the language's sound changes that predate the word.
+    /// Only later sound changes apply to it.
+    pub sound_change_stage: usize,
+}
+
+/// Basic vocabulary shared by most languages, after Swadesh's list.
+pub const CORE_VOCABULARY: &[&str] = &[
+    "i", "you", "we", "this", "that", "who", "what", "not", "all", "many", "one", "two", "big",
+    "long", "small", "woman", "man", "person", "fish", "bird", "dog", "louse", "tree", "seed",
+    "leaf", "root", "bark", "skin", "flesh", "blood", "bone", "grease", "egg", "horn", "tail",
+    "feather", "hair", "head", "ear", "eye", "nose", "mouth", "tooth", "tongue", "claw", "foot",
+    "knee", "hand", "belly", "neck", "breast", "heart", "liver", "drink", "eat", "bite", "see",
+    "hear", "know", "sleep", "die", "kill", "swim", "fly", "walk", "come", "lie", "sit", "stand",
+    "give", "say", "sun", "moon", "star", "water", "rain", "stone", "sand", "earth", "cloud",
+    "smoke", "fire", "ash", "burn", "path", "mountain", "red", "green", "yellow", "white", "black",
+    "night", "hot", "cold", "full", "new", "good", "round", "dry", "name",
+];
+
+/// Explicit words that override generation for their concepts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lexicon {
+    entries: BTreeMap<String, LexicalEntry>,
+}
+
+impl Lexicon {
+    /// Create an empty lexicon.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the entry for a concept.
+    pub fn get(&self, concept: &str) -> Option<&LexicalEntry> {
+        self.entries.get(concept)
+    }
+
+    /// Add or replace the entry for a concept.
+    pub fn insert(&mut self, concept: &str, entry: LexicalEntry) {
+        self.entries.insert(concept.to_string(), entry);
+    }
+
+    /// Iterate over all entries, ordered by concept.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &LexicalEntry)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Number of explicit entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether the lexicon has no explicit entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl HeapSize for Lexicon {
+    fn heap_size(&self) -> usize {
+        btree_map_size::<String, LexicalEntry>(self.entries.len())
+            + self
+                .entries
+                .iter()
+                .map(|(concept, entry)| concept.heap_size() + entry.heap_size())
+                .sum::<usize>()
+    }
+}
+
+/// Semantic fields that tend to be borrowed together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SemanticField {
+    /// Markets, money and merchants
+    Trade,
+    /// Gods, temples and worship
+    Religion,
+    /// Weapons, armies and battle
+    Warfare,
+    /// Rulers, law and the state
+    Governance,
+    /// Metals, tools and workshops
+    Craft,
+    /// Food and drink
+    Food,
+    /// Basic words for the natural world
+    Nature,
+    /// Family relations
+    Kinship,
+}
+
+impl SemanticField {
+    /// Get all semantic fields.
+    pub fn all() -> &'static [SemanticField] {
+        &[
+            SemanticField::Trade,
+            SemanticField::Religion,
+            SemanticField::Warfare,
+            SemanticField::Governance,
+            SemanticField::Craft,
+            SemanticField::Food,
+            SemanticField::Nature,
+            SemanticField::Kinship,
+        ]
+    }
+
+    /// The concepts belonging to this field.
+    pub fn concepts(&self) -> &'static [&'static str] {
+        match self {
+            SemanticField::Trade => &[
+                "market", "coin", "price", "merchant", "buy", "sell", "debt", "silver", "gold",
+                "salt", "cloth", "ship", "scale", "contract",
+            ],
+            SemanticField::Religion => &[
+                "god",
+                "temple",
+                "priest",
+                "prayer",
+                "spirit",
+                "sacrifice",
+                "holy",
+                "sin",
+                "altar",
+                "blessing",
+                "heaven",
+                "oracle",
+            ],
+            SemanticField::Warfare => &[
+                "sword", "spear", "shield", "army", "battle", "fortress", "captain", "arrow",
+                "armor", "siege", "soldier", "victory",
+            ],
+            SemanticField::Governance => &[
+                "king", "queen", "law", "tax", "court", "judge", "council", "city", "border",
+                "crown", "throne", "decree",
+            ],
+            SemanticField::Craft => &[
+                "iron", "bronze", "forge", "hammer", "wheel", "pottery", "loom", "glass", "brick",
+                "tool",
+            ],
+            SemanticField::Food => &[
+                "bread", "wine", "beer", "cheese", "oil", "honey", "wheat", "rice", "meat",
+                "fruit", "spice",
+            ],
+            SemanticField::Nature => &[
+                "water", "fire", "sun", "moon", "star", "tree", "stone", "river", "mountain",
+                "sea", "rain", "wind",
+            ],
+            SemanticField::Kinship => &[
+                "mother", "father", "child", "brother", "sister", "son", "daughter", "wife",
+                "husband", "elder",
+            ],
+        }
+    }
+
+    /// How readily this field is borrowed (0-1).
+    ///
+    /// Cultural vocabulary moves easily between languages, while basic
+    /// vocabulary like kinship terms and natural phenomena resists borrowing.
+    pub fn borrowability(&self) -> f32 {
+        match self {
+            SemanticField::Trade => 1.0,
+            SemanticField::Religion => 0.9,
+            SemanticField::Governance => 0.8,
+            SemanticField::Warfare => 0.7,
+            SemanticField::Craft => 0.7,
+            SemanticField::Food => 0.6,
+            SemanticField::Nature => 0.2,
+            SemanticField::Kinship => 0.1,
+        }
+    }
+}
+
+/// A wave of lexical replacement.
+///
+/// Every concept is independently replaced with probability `rate`, decided by a
+/// hash of the concept and the shift's seed. Because the decision never depends on
+/// anything else, a shift applies consistently to an unbounded vocabulary, and a
+/// higher rate with the same seed replaces a superset of the same concepts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LexicalShift {
+    /// Seed identifying this wave of replacement
+    pub seed: u64,
+    /// Fraction of the vocabulary replaced (0-1)
+    pub rate: f32,
+}
+
+impl LexicalShift {
+    /// Create a new lexical shift.
+    pub fn new(seed: u64, rate: f32) -> Self {
+        Self { seed, rate }
+    }
+
+    /// Check whether this shift replaces the word for a concept, in a language
+    /// using the given algorithm version.
+    pub fn replaces(&self, concept: &str, version: AlgorithmVersion) -> bool {
+        let mut rng = version.rng(version.hash(concept, self.seed));
+        (rng.next() as f32) < self.rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replacement_rate() {
+        let shift = LexicalShift::new(99, 0.3);
+        let replaced = (0..1000)
+            .filter(|i| shift.replaces(&format!("concept{}", i), AlgorithmVersion::LATEST))
+            .count();
+
+        assert!(replaced > 200 && replaced < 400, "replaced {}", replaced);
+    }
+
+    #[test]
+    fn test_higher_rate_is_superset() {
+        let low = LexicalShift::new(7, 0.1);
+        let high = LexicalShift::new(7, 0.4);
+
+        for i in 0..500 {
+            let concept = format!("concept{}", i);
+            if low.replaces(&concept, AlgorithmVersion::LATEST) {
+                assert!(high.replaces(&concept, AlgorithmVersion::LATEST));
+            }
+        }
+    }
+}
@@ -36,25 +36,81 @@
 //! // The same input always produces the same output
 //! assert_eq!(word, language.translate_word("house"));
 //! ```
+//!
+//! ## `no_std`
+//!
+//! Without the default `std` feature the crate needs only `core` and `alloc`.
+//! Languages then have no word cache, are not `Sync`, and cannot be saved to or
+//! loaded from readers and writers.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
+mod batch;
+mod builder;
+#[cfg(feature = "std")]
+mod cache;
+mod comparative;
+mod contact;
+mod creole;
 mod culture;
+mod dialect;
+mod drift;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "serde")]
+mod format;
 mod generation;
 mod genome;
 mod language;
+mod lexicon;
+mod memory;
 mod morphology;
 pub mod naming;
 mod phonology;
 mod seeded_rng;
+mod sound_change;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+pub use builder::LanguageBuilder;
+#[cfg(feature = "std")]
+pub use cache::{CacheConfig, CacheStats, EvictionPolicy};
+pub use comparative::{CognateSet, ComparativeWordlist, ComparisonEntry, SoundCorrespondence};
+pub use contact::Loanword;
 pub use culture::{CulturalProfile, Geography};
-pub use genome::{LinguisticGenome, MorphologyType, WordOrder};
+pub use dialect::DialectPoint;
+pub use drift::{Affix, AffixPosition, GrammaticalFunction};
+pub use error::Error;
+#[cfg(feature = "serde")]
+pub use format::{FormatError, FORMAT_VERSION};
+pub use genome::{AlgorithmVersion, LinguisticGenome, MorphologyType, WordOrder};
 pub use language::Language;
+pub use lexicon::{
+    Etymology, LexicalEntry, LexicalShift, Lexicon, SemanticField, CORE_VOCABULARY,
+};
+pub use memory::MemoryUsage;
 pub use morphology::{CombiningRule, Morpheme, MorphemeDatabase, MorphemeType};
 pub use naming::{
+    birth_order::BirthOrderNaming,
     epithet::{Characteristic, EpithetContext},
+    gender::{Gender, GenderMarking},
     personal::PersonalNameContext,
     place::{PlaceNameContext, PlaceType},
+    registry::NameRegistry,
+    surname::{HouseOrigin, SurnameInheritance},
     NamePattern, NamingSystem,
 };
-pub use phonology::{Consonant, PhonemeInventory, SyllableStructure, Vowel};
+pub use phonology::{
+    Consonant, Manner, PhonemeFeatures, PhonemeInventory, Phonotactics, ProsodicSystem,
+    StressPattern, SyllableStructure, Vowel,
+};
+pub use seeded_rng::{
+    stable_hash, HashKind, JsLcg, RandomSource, RngKind, SeededRng, Xoshiro256StarStar,
+};
+pub use sound_change::{Environment, SoundChange};
+#[cfg(feature = "wasm")]
+pub use wasm::WasmLanguage;
 
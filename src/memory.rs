@@ -0,0 +1,238 @@
+//! Accounting for the memory a language uses.
+//!
+//! Sizes are estimates: vectors and strings are counted by their capacity and
+//! hash maps by their buckets, but ordered maps only by their entries, without
+//! the allocator's own bookkeeping.
+
+use crate::drift::Affix;
+use crate::genome::LinguisticGenome;
+use crate::lexicon::{Etymology, LexicalEntry, LexicalShift};
+use crate::phonology::{Consonant, PhonemeCategory, PhonemeInventory, SyllableStructure, Vowel};
+use crate::sound_change::SoundChange;
+use alloc::{string::String, vec::Vec};
+use core::mem::size_of;
+
+/// How many bytes a [`Language`](crate::Language) uses, by part.
+///
+/// # Example
+///
+/// ```
+/// use phyla_lang::{CulturalProfile, Geography, Language};
+///
+/// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+/// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+/// language.translate_word("water");
+///
+/// let usage = language.memory_usage();
+/// assert!(usage.cache > 0);
+/// println!("{} bytes", usage.total());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage {
+    /// The `Language` value itself, its ID and its consonant tables
+    pub language: usize,
+    /// Heap memory of the genome
+    pub genome: usize,
+    /// Heap memory of the naming system, including its copy of the genome
+    pub naming: usize,
+    /// Heap memory of the word cache
+    pub cache: usize,
+}
+
+impl MemoryUsage {
+    /// Bytes used altogether.
+    pub fn total(&self) -> usize {
+        self.language + self.genome + self.naming + self.cache
+    }
+}
+
+/// Heap memory owned by a value, not counting the value itself.
+pub(crate) trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+/// Estimated heap memory of an ordered map's entries.
+pub(crate) fn btree_map_size<K, V>(len: usize) -> usize {
+    len * (size_of::<K>() + size_of::<V>())
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+/// Implement [`HeapSize`] for types that own no heap memory.
+macro_rules! no_heap {
+    ($($ty:ty),*) => {
+        $(impl HeapSize for $ty {
+            fn heap_size(&self) -> usize {
+                0
+            }
+        })*
+    };
+}
+
+no_heap!(f32, PhonemeCategory, SyllableStructure, LexicalShift);
+
+impl HeapSize for Consonant {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
+impl HeapSize for Vowel {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
+impl HeapSize for PhonemeInventory {
+    fn heap_size(&self) -> usize {
+        self.stops.heap_size()
+            + self.fricatives.heap_size()
+            + self.nasals.heap_size()
+            + self.liquids.heap_size()
+            + self.glides.heap_size()
+            + self.vowels.heap_size()
+            + self.category_weights.heap_size()
+    }
+}
+
+impl HeapSize for SoundChange {
+    fn heap_size(&self) -> usize {
+        self.from.heap_size() + self.to.heap_size()
+    }
+}
+
+impl HeapSize for Etymology {
+    fn heap_size(&self) -> usize {
+        match self {
+            Etymology::Native | Etymology::Innovation => 0,
+            Etymology::Borrowed { donor, source_form } => {
+                donor.heap_size() + source_form.heap_size()
+            }
+            Etymology::Inherited {
+                source,
+                source_form,
+            } => source.heap_size() + source_form.heap_size(),
+        }
+    }
+}
+
+impl HeapSize for LexicalEntry {
+    fn heap_size(&self) -> usize {
+        self.form.heap_size() + self.etymology.heap_size()
+    }
+}
+
+impl HeapSize for Affix {
+    fn heap_size(&self) -> usize {
+        self.form.heap_size()
+    }
+}
+
+impl HeapSize for LinguisticGenome {
+    fn heap_size(&self) -> usize {
+        self.phoneme_inventory.heap_size()
+            + self.syllable_patterns.heap_size()
+            + self.sound_changes.heap_size()
+            + self.lexical_shifts.heap_size()
+            + self.lexicon.heap_size()
+            + self.loan_phonemes.heap_size()
+            + self.affixes.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::{CulturalProfile, Geography};
+    use crate::language::Language;
+
+    fn language() -> Language {
+        let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+        Language::from_culture(culture, Geography::Coastal, 12345)
+    }
+
+    #[test]
+    fn test_vec_counts_capacity_and_contents() {
+        let mut words = Vec::with_capacity(4);
+        words.push(String::with_capacity(10));
+        assert_eq!(words.heap_size(), 4 * size_of::<String>() + 10);
+    }
+
+    #[test]
+    fn test_parts_add_up() {
+        let usage = language().memory_usage();
+        assert!(usage.language >= size_of::<Language>());
+        assert!(usage.genome > 0);
+        // The naming system holds a copy of the genome and its morphemes
+        assert!(usage.naming > usage.genome);
+        assert_eq!(
+            usage.total(),
+            usage.language + usage.genome + usage.naming + usage.cache
+        );
+    }
+
+    #[test]
+    fn test_inherited_words_are_counted() {
+        let other = Language::from_culture(
+            CulturalProfile::new(2.0, 3.0, 4.0, 4.0, 2.0, 2.0),
+            Geography::Plains,
+            10,
+        );
+        let creole = Language::creole(&[(&language(), 0.6), (&other, 0.4)], 99);
+        // Clones, so that both have strings without spare capacity
+        let genome = creole.genome.clone();
+        let mut stripped = genome.clone();
+        let mut inherited = 0;
+        for (concept, entry) in genome.lexicon.iter() {
+            if let Etymology::Inherited {
+                source,
+                source_form,
+            } = &entry.etymology
+            {
+                inherited += source.heap_size() + source_form.heap_size();
+                let etymology = Etymology::Inherited {
+                    source: String::new(),
+                    source_form: String::new(),
+                };
+                stripped.lexicon.insert(
+                    concept,
+                    LexicalEntry {
+                        etymology,
+                        ..entry.clone()
+                    },
+                );
+            }
+        }
+
+        assert!(inherited > 0);
+        assert_eq!(genome.heap_size(), stripped.heap_size() + inherited);
+        assert!(creole.memory_usage().genome >= genome.heap_size());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_cache_growth_is_counted() {
+        let language = language();
+        let before = language.memory_usage();
+        for concept in crate::lexicon::CORE_VOCABULARY {
+            language.translate_word(concept);
+        }
+        let after = language.memory_usage();
+
+        assert!(after.cache > before.cache);
+        assert_eq!(after.genome, before.genome);
+
+        language.clear_cache();
+        assert!(language.memory_usage().cache < after.cache);
+    }
+}
@@ -5,12 +5,15 @@
 
 use crate::culture::{CulturalProfile, Geography};
 use crate::generation::generate_word;
+use crate::error::Error;
 use crate::genome::LinguisticGenome;
+use crate::memory::{btree_map_size, HeapSize};
 use crate::seeded_rng::SeededRng;
-use std::collections::HashMap;
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
 
 /// The semantic type of a morpheme - what it means conceptually.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MorphemeType {
     // Natural elements
     Fire,
@@ -76,10 +79,34 @@ pub enum MorphemeType {
     Death,
     Time,
     Fate,
+    
+    // Kinship
+    Son,
+    Daughter,
+    Child,
+    Father,
+    Mother,
+    Parent,
+    
+    // Numerals
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
 }
 
 impl MorphemeType {
-    /// Get all morpheme types as a slice.
+    /// Get all morpheme types names are built from, as a slice.
+    ///
+    /// Kinship terms and numerals only mark lineage and birth order, so they are
+    /// listed separately by [`kinship`](Self::kinship) and
+    /// [`numerals`](Self::numerals).
     pub fn all() -> &'static [MorphemeType] {
         &[
             // Elements
@@ -107,6 +134,28 @@ impl MorphemeType {
         ]
     }
     
+    /// Get the kinship morpheme types, which lineage names are built from.
+    pub fn kinship() -> &'static [MorphemeType] {
+        &[
+            MorphemeType::Son, MorphemeType::Daughter, MorphemeType::Child,
+            MorphemeType::Father, MorphemeType::Mother, MorphemeType::Parent,
+        ]
+    }
+    
+    /// Get the numerals from one to ten, in order.
+    pub fn numerals() -> &'static [MorphemeType] {
+        &[
+            MorphemeType::One, MorphemeType::Two, MorphemeType::Three, MorphemeType::Four,
+            MorphemeType::Five, MorphemeType::Six, MorphemeType::Seven, MorphemeType::Eight,
+            MorphemeType::Nine, MorphemeType::Ten,
+        ]
+    }
+    
+    /// Get the numeral for a number from one to ten.
+    pub fn numeral(number: usize) -> Option<MorphemeType> {
+        number.checked_sub(1).and_then(|i| Self::numerals().get(i)).copied()
+    }
+    
     /// Convert to a string key for word generation.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -165,6 +214,22 @@ impl MorphemeType {
             MorphemeType::Death => "death",
             MorphemeType::Time => "time",
             MorphemeType::Fate => "fate",
+            MorphemeType::Son => "son",
+            MorphemeType::Daughter => "daughter",
+            MorphemeType::Child => "child",
+            MorphemeType::Father => "father",
+            MorphemeType::Mother => "mother",
+            MorphemeType::Parent => "parent",
+            MorphemeType::One => "one",
+            MorphemeType::Two => "two",
+            MorphemeType::Three => "three",
+            MorphemeType::Four => "four",
+            MorphemeType::Five => "five",
+            MorphemeType::Six => "six",
+            MorphemeType::Seven => "seven",
+            MorphemeType::Eight => "eight",
+            MorphemeType::Nine => "nine",
+            MorphemeType::Ten => "ten",
         }
     }
     
@@ -254,6 +319,7 @@ impl MorphemeType {
 
 /// A morpheme - a sound paired with meaning and cultural weight.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Morpheme {
     /// The sound form of this morpheme in this language
     pub form: String,
@@ -265,8 +331,9 @@ pub struct Morpheme {
 
 /// A database of morphemes for a language, indexed by meaning.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MorphemeDatabase {
-    morphemes: HashMap<MorphemeType, Morpheme>,
+    morphemes: BTreeMap<MorphemeType, Morpheme>,
 }
 
 impl MorphemeDatabase {
@@ -276,7 +343,7 @@ impl MorphemeDatabase {
         culture: &CulturalProfile,
         geography: &Geography,
     ) -> Self {
-        let mut morphemes = HashMap::new();
+        let mut morphemes = BTreeMap::new();
         
         for &meaning in MorphemeType::all() {
             let form = generate_word(genome, meaning.as_str());
@@ -298,13 +365,19 @@ impl MorphemeDatabase {
     }
     
     /// Select a weighted random morpheme suitable for naming.
-    pub fn select_weighted(&self, rng: &mut SeededRng, _geography: &Geography) -> &Morpheme {
-        // Get all morphemes with their weights
+    ///
+    /// Returns [`Error::EmptyChoice`] if the database has no morphemes.
+    pub fn select_weighted(
+        &self,
+        rng: &mut SeededRng,
+        _geography: &Geography,
+    ) -> Result<&Morpheme, Error> {
+        // Get all morphemes with their weights, in a fixed order
         let morphemes: Vec<&Morpheme> = self.morphemes.values().collect();
         let weights: Vec<f32> = morphemes.iter().map(|m| m.weight).collect();
         
-        let idx = rng.weighted_choice(&weights);
-        morphemes[idx]
+        let idx = rng.weighted_choice(&weights)?;
+        Ok(morphemes[idx])
     }
     
     /// Get morphemes of specific types.
@@ -318,13 +391,21 @@ impl MorphemeDatabase {
         }
         
         let weights: Vec<f32> = available.iter().map(|m| m.weight).collect();
-        let idx = rng.weighted_choice(&weights);
+        let idx = rng.weighted_choice(&weights).ok()?;
         Some(available[idx])
     }
 }
 
+impl HeapSize for MorphemeDatabase {
+    fn heap_size(&self) -> usize {
+        btree_map_size::<MorphemeType, Morpheme>(self.morphemes.len())
+            + self.morphemes.values().map(|m| m.form.heap_size()).sum::<usize>()
+    }
+}
+
 /// Rules for combining morphemes into names.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CombiningRule {
     /// Simple concatenation: "Fire" + "Stone" = "Firestone"
     Concatenate,
@@ -373,6 +454,15 @@ mod tests {
         assert_eq!(MorphemeType::Mountain.as_str(), "mountain");
     }
 
+    #[test]
+    fn test_numerals() {
+        assert_eq!(MorphemeType::numeral(1), Some(MorphemeType::One));
+        assert_eq!(MorphemeType::numeral(10), Some(MorphemeType::Ten));
+        assert_eq!(MorphemeType::numeral(0), None);
+        assert_eq!(MorphemeType::numeral(11), None);
+        assert!(!MorphemeType::all().contains(&MorphemeType::One));
+    }
+
     #[test]
     fn test_cultural_weights() {
         let culture = CulturalProfile::new(4.0, 3.0, 3.0, 3.0, 3.0, 3.0);
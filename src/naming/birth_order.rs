@@ -0,0 +1,209 @@
+//! Birth order in personal names, spelled with the language's own numerals.
+
+use super::personal::PersonalNameContext;
+use super::NamingSystem;
+use crate::culture::CulturalProfile;
+use crate::genome::AlgorithmVersion;
+use crate::morphology::MorphemeType;
+use alloc::format;
+use alloc::string::String;
+
+/// Birth-order titles repeat after this many children, as in Bali.
+const PREFIX_CYCLE: usize = 4;
+
+/// How a culture shows birth order in personal names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BirthOrderNaming {
+    /// Birth order does not appear in names
+    #[default]
+    Ignored,
+    /// An ordinal name after the given name, marked for gender, as Roman
+    /// Secundus and Tertia
+    Ordinal,
+    /// A title before the given name that counts the first four children and
+    /// then starts over, as Balinese Wayan, Made, Nyoman and Ketut
+    Prefix,
+    /// The firstborn is the heir: titled "first" and named after the parent
+    Heir,
+}
+
+impl BirthOrderNaming {
+    /// Determine how birth order is named from cultural traits.
+    pub fn from_culture(culture: &CulturalProfile) -> Self {
+        // Low honesty-humility = inheritance by the firstborn
+        if culture.normalized_honesty_humility() < 0.375 {
+            return BirthOrderNaming::Heir;
+        }
+
+        // Very high conscientiousness = children counted in order
+        if culture.normalized_conscientiousness() > 0.7 {
+            return BirthOrderNaming::Ordinal;
+        }
+
+        // Sociable, cooperative cultures = a child's place is said aloud
+        if culture.normalized_extraversion() > 0.6 && culture.normalized_agreeableness() > 0.6 {
+            return BirthOrderNaming::Prefix;
+        }
+
+        BirthOrderNaming::Ignored
+    }
+
+    /// Determine how birth order is named from cultural traits as a version of
+    /// the algorithms did.
+    ///
+    /// Versions before 5 ignore birth order.
+    pub fn from_culture_with_version(culture: &CulturalProfile, version: AlgorithmVersion) -> Self {
+        if version >= AlgorithmVersion::V5 {
+            Self::from_culture(culture)
+        } else {
+            BirthOrderNaming::Ignored
+        }
+    }
+}
+
+impl NamingSystem {
+    /// Get the language's word for a number from one to ten.
+    pub fn numeral_word(&self, number: usize) -> Option<String> {
+        MorphemeType::numeral(number).map(|numeral| self.word(numeral.as_str()))
+    }
+
+    /// Get the name given for a birth order, capitalized.
+    ///
+    /// Children past the tenth share the tenth's name.
+    pub fn ordinal_name(&self, birth_order: usize) -> String {
+        let word = self
+            .numeral_word(birth_order.clamp(1, MorphemeType::numerals().len()))
+            .unwrap_or_default();
+        Self::capitalize_first_letter(&word)
+    }
+
+    /// Add birth order to a given name, following the culture's
+    /// [`BirthOrderNaming`].
+    ///
+    /// Birth order counts from 1 for the firstborn; 0 is read as 1.
+    pub(crate) fn mark_birth_order(&self, name: String, context: &PersonalNameContext) -> String {
+        let Some(order) = context.birth_order.map(|order| order.max(1)) else {
+            return name;
+        };
+
+        match self.birth_order_naming {
+            BirthOrderNaming::Ignored => name,
+            BirthOrderNaming::Ordinal => {
+                let ordinal = self.mark_gender(&self.ordinal_name(order), context.gender);
+                format!("{} {}", name, ordinal)
+            }
+            BirthOrderNaming::Prefix => {
+                let title = self.ordinal_name((order - 1) % PREFIX_CYCLE + 1);
+                format!("{} {}", title, name)
+            }
+            BirthOrderNaming::Heir if order == 1 => {
+                let name = match &context.parent_name {
+                    Some(parent) => self.mark_gender(parent, context.gender),
+                    None => name,
+                };
+                format!("{} {}", self.ordinal_name(1), name)
+            }
+            BirthOrderNaming::Heir => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::Geography;
+    use crate::genome::LinguisticGenome;
+    use crate::naming::gender::{Gender, GenderMarking};
+    use crate::naming::tests::plain_naming;
+    use crate::naming::NamePattern;
+
+    fn naming(birth_order_naming: BirthOrderNaming) -> NamingSystem {
+        NamingSystem {
+            birth_order_naming,
+            ..plain_naming()
+        }
+    }
+
+    fn child(order: usize) -> PersonalNameContext {
+        PersonalNameContext::with_parent(42, "Thorin".into()).with_birth_order(order)
+    }
+
+    #[test]
+    fn test_ordinal_names() {
+        let mut naming = naming(BirthOrderNaming::Ordinal);
+        let given = naming.generate_simple_name(42);
+        let second = naming.ordinal_name(2);
+        assert_eq!(
+            second,
+            NamingSystem::capitalize_first_letter(&naming.numeral_word(2).unwrap())
+        );
+        assert_ne!(second, naming.ordinal_name(3));
+        assert_eq!(naming.ordinal_name(12), naming.ordinal_name(10));
+        assert_eq!(
+            naming.generate_personal_name(&child(2)),
+            format!("{} {}", given, second)
+        );
+
+        // Ordinals agree with the child's gender
+        naming.gender_marking = GenderMarking::Endings;
+        let (feminine, _) = naming.gender_endings();
+        let daughter = naming.generate_personal_name(&child(2).with_gender(Gender::Female));
+        assert!(daughter.ends_with(&feminine));
+    }
+
+    #[test]
+    fn test_prefixes_cycle() {
+        let naming = naming(BirthOrderNaming::Prefix);
+        let given = naming.generate_simple_name(42);
+        let first = naming.generate_personal_name(&child(1));
+        assert_eq!(first, format!("{} {}", naming.ordinal_name(1), given));
+        assert_eq!(naming.generate_personal_name(&child(5)), first);
+        assert_ne!(naming.generate_personal_name(&child(2)), first);
+    }
+
+    #[test]
+    fn test_heirs() {
+        let naming = naming(BirthOrderNaming::Heir);
+        let given = naming.generate_simple_name(42);
+        assert_eq!(
+            naming.generate_personal_name(&child(1)),
+            format!("{} Thorin", naming.ordinal_name(1))
+        );
+        assert_eq!(naming.generate_personal_name(&child(2)), given);
+        assert_eq!(
+            naming.generate_personal_name(&PersonalNameContext::simple(42)),
+            given
+        );
+    }
+
+    #[test]
+    fn test_elaborate_names_drop_english_ordinals() {
+        let mut naming = naming(BirthOrderNaming::Ordinal);
+        naming.pattern = NamePattern::Elaborate;
+        for id in 0..20 {
+            let context = PersonalNameContext::simple(id).with_birth_order(3);
+            let name = naming.generate_personal_name(&context);
+            assert!(name.contains(&naming.ordinal_name(3)));
+            for ordinal in ["First", "Second", "Third", "Fourth", "Fifth"] {
+                assert!(!name.ends_with(&format!("the {}", ordinal)), "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_older_versions_ignore_birth_order() {
+        let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 1.5, 3.0);
+        assert_eq!(
+            BirthOrderNaming::from_culture(&culture),
+            BirthOrderNaming::Heir
+        );
+
+        for version in [AlgorithmVersion::V3, AlgorithmVersion::V4] {
+            let genome =
+                LinguisticGenome::from_culture_with_version(culture, Geography::Plains, 7, version);
+            let naming = NamingSystem::new(genome, culture, Geography::Plains);
+            assert_eq!(naming.birth_order_naming, BirthOrderNaming::Ignored);
+        }
+    }
+}
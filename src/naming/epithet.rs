@@ -6,9 +6,16 @@
 use super::NamingSystem;
 use crate::morphology::MorphemeType;
 use crate::seeded_rng::SeededRng;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 
 /// Context for generating an epithet.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EpithetContext {
     /// Entity ID for determinism
     pub entity_id: u64,
@@ -22,6 +29,7 @@ pub struct EpithetContext {
 
 /// A defining characteristic that could generate an epithet.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Characteristic {
     // Physical
     Tall,
@@ -49,7 +57,7 @@ pub enum Characteristic {
 
 impl Characteristic {
     /// Map to morpheme types.
-    fn to_morpheme_types(&self) -> Vec<MorphemeType> {
+    fn to_morpheme_types(self) -> Vec<MorphemeType> {
         match self {
             Characteristic::Tall => vec![MorphemeType::Great, MorphemeType::Sky],
             Characteristic::Short => vec![MorphemeType::Small],
@@ -108,7 +116,7 @@ impl NamingSystem {
         // High openness cultures love epithets
         // Low openness cultures rarely use them
         let openness = self.culture.normalized_openness();
-        let mut rng = SeededRng::new(context.entity_id ^ self.genome.seed);
+        let mut rng = self.genome.algorithm_version.rng(context.entity_id ^ self.genome.seed);
         
         let epithet_probability = openness;
         if rng.next() as f32 > epithet_probability {
@@ -120,10 +128,11 @@ impl NamingSystem {
             Some(self.generate_achievement_epithet(achievement, &mut rng))
         } else if let Some(birth) = &context.birth_event {
             Some(self.generate_birth_epithet(birth, &mut rng))
-        } else if let Some(characteristic) = &context.characteristic {
-            Some(self.generate_characteristic_epithet(characteristic, &mut rng))
         } else {
-            None
+            context
+                .characteristic
+                .as_ref()
+                .map(|characteristic| self.generate_characteristic_epithet(characteristic, &mut rng))
         }
     }
     
@@ -0,0 +1,227 @@
+//! Gender in personal names: how, and whether, a culture marks it.
+
+use super::NamingSystem;
+use crate::culture::CulturalProfile;
+use crate::morphology::MorphemeType;
+use crate::phonology::is_vowel_symbol;
+use alloc::string::String;
+
+/// Key for the seed of gender endings: "GENDER" in ASCII.
+const ENDINGS_KEY: u64 = 0x4745_4E44_4552;
+
+/// The gender of a person being named.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Gender {
+    /// Takes feminine names and the word for "daughter"
+    Female,
+    /// Takes masculine names and the word for "son"
+    Male,
+    /// Takes the unmarked form of every name
+    Neutral,
+}
+
+/// How a culture marks gender in personal names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GenderMarking {
+    /// Names do not mark gender; everyone draws from one pool
+    #[default]
+    Unmarked,
+    /// Characteristic endings, as Latin Julia and Julius
+    Endings,
+    /// Affixes worn down from the words for "daughter" and "son", as Japanese
+    /// names in *-ko* ("child")
+    Morphemes,
+}
+
+impl GenderMarking {
+    /// Determine how gender is marked from cultural traits.
+    pub fn from_culture(culture: &CulturalProfile) -> Self {
+        // Open, humble cultures = no gender marking
+        if culture.normalized_openness() > 0.6 && culture.normalized_honesty_humility() > 0.6 {
+            return GenderMarking::Unmarked;
+        }
+
+        // High conscientiousness = regular endings
+        if culture.normalized_conscientiousness() > 0.6 {
+            return GenderMarking::Endings;
+        }
+
+        // High agreeableness = names built around family words
+        if culture.normalized_agreeableness() > 0.6 {
+            return GenderMarking::Morphemes;
+        }
+
+        GenderMarking::Endings
+    }
+}
+
+impl NamingSystem {
+    /// Get the feminine and masculine name endings of this language.
+    ///
+    /// Feminine names end in a vowel, masculine names in a different vowel and
+    /// a consonant, all taken from the language's inventory.
+    pub fn gender_endings(&self) -> (String, String) {
+        let inventory = &self.genome.phoneme_inventory;
+        let mut rng = self
+            .genome
+            .algorithm_version
+            .rng(self.genome.seed ^ ENDINGS_KEY);
+
+        let vowels = &inventory.vowels;
+        if vowels.is_empty() {
+            return (String::new(), String::new());
+        }
+        let feminine = rng.range(0, vowels.len());
+        // A different vowel if there is one
+        let masculine = (feminine + 1 + rng.range(0, vowels.len().max(2) - 1)) % vowels.len();
+
+        let codas = if inventory.nasals.is_empty() {
+            &inventory.stops
+        } else {
+            &inventory.nasals
+        };
+        let mut masculine_ending = vowels[masculine].0.clone();
+        if !codas.is_empty() {
+            masculine_ending.push_str(&codas[rng.range(0, codas.len())].0);
+        }
+
+        (vowels[feminine].0.clone(), masculine_ending)
+    }
+
+    /// Mark the gender of a person on a name, following the culture's
+    /// [`GenderMarking`].
+    ///
+    /// Names of unknown or neutral gender, and names in cultures that do not
+    /// mark gender, are returned unchanged.
+    pub fn mark_gender(&self, name: &str, gender: Option<Gender>) -> String {
+        let female = match gender {
+            Some(Gender::Female) => true,
+            Some(Gender::Male) => false,
+            Some(Gender::Neutral) | None => return name.into(),
+        };
+
+        match self.gender_marking {
+            GenderMarking::Unmarked => name.into(),
+            GenderMarking::Endings => {
+                let (feminine, masculine) = self.gender_endings();
+                let ending = if female { feminine } else { masculine };
+
+                // The ending replaces the stem's final vowels
+                let segments = self.genome.segment(name);
+                let stem = match segments.iter().rposition(|s| !is_vowel_symbol(s)) {
+                    Some(last) => segments[..=last].concat(),
+                    None => name.into(),
+                };
+                stem + &ending
+            }
+            GenderMarking::Morphemes => {
+                let kin = if female {
+                    MorphemeType::Daughter
+                } else {
+                    MorphemeType::Son
+                };
+                String::from(name) + &self.lineage_affix(kin)
+            }
+        }
+    }
+
+    /// Pick the kinship term for a person's gender in this culture.
+    ///
+    /// Cultures that do not mark gender, and people of neutral or unknown
+    /// gender, get the `neutral` term.
+    pub(crate) fn gendered_kin(
+        &self,
+        gender: Option<Gender>,
+        female: MorphemeType,
+        male: MorphemeType,
+        neutral: MorphemeType,
+    ) -> MorphemeType {
+        match (self.gender_marking, gender) {
+            (GenderMarking::Unmarked, _) => neutral,
+            (_, Some(Gender::Female)) => female,
+            (_, Some(Gender::Male)) => male,
+            _ => neutral,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::naming::personal::PersonalNameContext;
+    use crate::naming::tests::plain_naming;
+    use crate::naming::NamePattern;
+
+    fn naming(gender_marking: GenderMarking) -> NamingSystem {
+        NamingSystem {
+            gender_marking,
+            ..plain_naming()
+        }
+    }
+
+    fn names(naming: &NamingSystem, id: u64) -> [String; 3] {
+        let context = PersonalNameContext::simple(id);
+        [
+            naming.generate_personal_name(&context),
+            naming.generate_personal_name(&context.clone().with_gender(Gender::Female)),
+            naming.generate_personal_name(&context.with_gender(Gender::Male)),
+        ]
+    }
+
+    #[test]
+    fn test_endings() {
+        let naming = naming(GenderMarking::Endings);
+        let (feminine, masculine) = naming.gender_endings();
+        assert!(!feminine.is_empty());
+        assert_ne!(feminine, masculine);
+
+        for id in 0..20 {
+            let [unknown, female, male] = names(&naming, id);
+            assert_eq!(unknown, naming.generate_simple_name(id));
+            assert!(female.ends_with(&feminine));
+            assert!(male.ends_with(&masculine));
+            assert_ne!(female, male);
+        }
+    }
+
+    #[test]
+    fn test_morphemes() {
+        let naming = naming(GenderMarking::Morphemes);
+        let daughter = naming.lineage_affix(MorphemeType::Daughter);
+        assert_ne!(daughter, naming.lineage_affix(MorphemeType::Son));
+        let [unknown, female, male] = names(&naming, 7);
+        assert_eq!(female, unknown + &daughter);
+        assert_ne!(female, male);
+    }
+
+    #[test]
+    fn test_unmarked_and_neutral_names() {
+        let naming = naming(GenderMarking::Unmarked);
+        let [unknown, female, male] = names(&naming, 7);
+        assert_eq!(female, unknown);
+        assert_eq!(male, unknown);
+
+        let naming = self::naming(GenderMarking::Endings);
+        let neutral = PersonalNameContext::simple(7).with_gender(Gender::Neutral);
+        assert_eq!(
+            naming.generate_personal_name(&neutral),
+            naming.generate_simple_name(7)
+        );
+    }
+
+    #[test]
+    fn test_gendered_lineage() {
+        let mut naming = naming(GenderMarking::Endings);
+        naming.pattern = NamePattern::Patronymic;
+        let son = PersonalNameContext::with_parent(7, "Thorin".into()).with_gender(Gender::Male);
+        let affix = naming.lineage_affix(MorphemeType::Son);
+        assert!(naming.generate_personal_name(&son).ends_with(&affix));
+
+        assert_eq!(
+            GenderMarking::from_culture(&CulturalProfile::new(3.0, 4.5, 3.0, 3.0, 4.5, 3.0)),
+            GenderMarking::Unmarked
+        );
+    }
+}
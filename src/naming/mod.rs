@@ -3,22 +3,46 @@
 //! This module provides the infrastructure for generating names that emerge from
 //! the same cultural and linguistic foundations as the language itself.
 
+pub mod birth_order;
 pub mod personal;
 pub mod place;
 pub mod epithet;
+pub mod gender;
+pub mod registry;
+pub mod surname;
 
 use crate::culture::{CulturalProfile, Geography};
-use crate::genome::LinguisticGenome;
+use crate::generation::{
+    generate_word_with, write_syllable, LazySyllableTable, StackStr, SyllableTable,
+};
+use crate::genome::{AlgorithmVersion, LinguisticGenome};
+use crate::memory::HeapSize;
 use crate::morphology::{CombiningRule, MorphemeDatabase};
-use crate::seeded_rng::{hash_deterministic, SeededRng};
+use birth_order::BirthOrderNaming;
+use gender::GenderMarking;
+use surname::SurnameInheritance;
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt::{self, Write};
 
 /// The pattern for generating names in a culture.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NamePattern {
     /// Simple given name (e.g., "Aria")
     Simple,
     /// Patronymic: Given + Father's name (e.g., "Aran Thorson")
     Patronymic,
+    /// Matronymic: Given + Mother's name (e.g., "Aran Meludottir")
+    Matronymic,
+    /// Bilateral: Given + Father's name + Mother's name (e.g., "Aran Thorson Meluson")
+    Bilateral,
+    /// Teknonymic: Given + "parent of" eldest child (e.g., "Aran, father of Thorin")
+    Teknonymic,
     /// Compound: Multiple morphemes (e.g., "Stormborn", "Ironheart")
     Compound,
     /// Elaborate: Title + Name + Lineage (e.g., "Lord Maxim the Third")
@@ -30,12 +54,23 @@ pub enum NamePattern {
 impl NamePattern {
     /// Determine the naming pattern from cultural traits.
     pub fn from_culture(culture: &CulturalProfile) -> Self {
+        Self::from_culture_with_version(culture, AlgorithmVersion::LATEST)
+    }
+    
+    /// Determine the naming pattern from cultural traits as a version of the
+    /// algorithms did.
+    ///
+    /// Versions before 4 only know simple, patronymic, compound and elaborate names.
+    pub fn from_culture_with_version(culture: &CulturalProfile, version: AlgorithmVersion) -> Self {
         let openness = culture.normalized_openness();
         let conscientiousness = culture.normalized_conscientiousness();
-        let honesty = culture.honesty_humility;
+        let honesty = culture.normalized_honesty_humility();
+        let agreeableness = culture.normalized_agreeableness();
+        let emotionality = culture.normalized_emotionality();
+        let lineages = version >= AlgorithmVersion::V4;
         
         // Low honesty-humility = elaborate names
-        if honesty < 2.5 {
+        if honesty < 0.375 {
             return NamePattern::Elaborate;
         }
         
@@ -44,11 +79,24 @@ impl NamePattern {
             return NamePattern::Compound;
         }
         
-        // High conscientiousness = structured patronymic
+        // High conscientiousness = structured lineage names
         if conscientiousness > 0.6 {
+            // Cooperative, emotionally expressive cultures trace descent through mothers
+            if lineages && agreeableness > 0.7 && emotionality > 0.6 {
+                return NamePattern::Matronymic;
+            }
+            // Somewhat open cultures keep both lines
+            if lineages && openness > 0.55 {
+                return NamePattern::Bilateral;
+            }
             return NamePattern::Patronymic;
         }
         
+        // Close-knit, expressive cultures know parents by their children
+        if lineages && agreeableness > 0.6 && emotionality > 0.7 {
+            return NamePattern::Teknonymic;
+        }
+        
         // Default: simple names
         NamePattern::Simple
     }
@@ -56,6 +104,7 @@ impl NamePattern {
 
 /// Configuration for the naming system derived from culture.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NamingSystem {
     /// The linguistic genome
     pub genome: LinguisticGenome,
@@ -71,6 +120,15 @@ pub struct NamingSystem {
     pub combining_rule: CombiningRule,
     /// Average syllables per name component
     pub syllables_per_name: usize,
+    /// How children inherit family names
+    pub surname_inheritance: SurnameInheritance,
+    /// How personal names mark gender
+    pub gender_marking: GenderMarking,
+    /// How personal names show birth order
+    pub birth_order_naming: BirthOrderNaming,
+    /// Consonant tables for the genome, built on first use
+    #[cfg_attr(feature = "serde", serde(skip))]
+    table: LazySyllableTable,
 }
 
 impl NamingSystem {
@@ -81,8 +139,12 @@ impl NamingSystem {
         geography: Geography,
     ) -> Self {
         let morphemes = MorphemeDatabase::from_genome(&genome, &culture, &geography);
-        let pattern = NamePattern::from_culture(&culture);
+        let pattern = NamePattern::from_culture_with_version(&culture, genome.algorithm_version);
         let combining_rule = CombiningRule::from_culture(&culture);
+        let surname_inheritance = SurnameInheritance::from_culture(&culture);
+        let gender_marking = GenderMarking::from_culture(&culture);
+        let birth_order_naming =
+            BirthOrderNaming::from_culture_with_version(&culture, genome.algorithm_version);
         
         // Name length influenced by geography and personality
         let syllables_per_name = Self::determine_name_length(&culture, &geography);
@@ -95,6 +157,10 @@ impl NamingSystem {
             pattern,
             combining_rule,
             syllables_per_name,
+            surname_inheritance,
+            gender_marking,
+            birth_order_naming,
+            table: LazySyllableTable::default(),
         }
     }
     
@@ -108,7 +174,7 @@ impl NamingSystem {
         }
         
         // Low honesty-humility = longer names
-        if culture.honesty_humility < 2.5 {
+        if culture.normalized_honesty_humility() < 0.375 {
             syllables += 1;
         }
         
@@ -122,94 +188,96 @@ impl NamingSystem {
             syllables += 1;
         }
         
-        syllables.max(1).min(4)
+        syllables.clamp(1, 4)
     }
     
     /// Generate a simple given name using the language's phonology.
     pub fn generate_simple_name(&self, seed: u64) -> String {
-        let concept = format!("name_{}", seed);
-        let word_seed = hash_deterministic(&concept, self.genome.seed);
-        let mut rng = SeededRng::new(word_seed);
-        
         let mut name = String::new();
-        
-        for _ in 0..self.syllables_per_name {
-            let syllable = self.generate_syllable(&mut rng);
-            name.push_str(&syllable);
-        }
-        
-        // Capitalize first letter
-        if let Some(first) = name.chars().next() {
-            name = first.to_uppercase().collect::<String>() + &name[first.len_utf8()..];
-        }
-        
+        // Writing to a `String` cannot fail
+        let _ = self.generate_simple_name_into(seed, &mut name);
         name
     }
-    
-    /// Generate a syllable for names (similar to word generation but tuned for names).
-    fn generate_syllable(&self, rng: &mut SeededRng) -> String {
-        let pattern = rng.choice(&self.genome.syllable_patterns);
-        let pattern_str = pattern.pattern();
-        
-        let mut syllable = String::new();
-        
-        for ch in pattern_str.chars() {
-            match ch {
-                'C' => {
-                    let consonant = self.choose_consonant(rng);
-                    syllable.push_str(&consonant);
-                }
-                'V' => {
-                    let vowel = rng.choice(&self.genome.phoneme_inventory.vowels);
-                    syllable.push_str(&vowel.0);
-                }
-                _ => {}
+
+    /// Write a simple given name to `out`, the same name
+    /// [`generate_simple_name`](Self::generate_simple_name) returns.
+    ///
+    /// Nothing is allocated unless the language has sound changes, so names can
+    /// be generated in bulk into a reused buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{CulturalProfile, Geography, Language};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    /// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+    ///
+    /// let mut name = String::new();
+    /// for id in 0..1000 {
+    ///     name.clear();
+    ///     language.naming.generate_simple_name_into(id, &mut name).unwrap();
+    ///     assert_eq!(name, language.naming.generate_simple_name(id));
+    /// }
+    /// ```
+    pub fn generate_simple_name_into<W: fmt::Write>(&self, seed: u64, out: &mut W) -> fmt::Result {
+        let table = self.table();
+        if !table.complete() {
+            return Ok(());
+        }
+
+        // "name_" and at most 20 digits
+        let mut concept = StackStr::<25>::new();
+        write!(concept, "name_{}", seed)?;
+        let word_seed = self.genome.algorithm_version.hash(concept.as_str(), self.genome.seed);
+        let mut rng = self.genome.algorithm_version.rng(word_seed);
+
+        let mut out = Capitalized::new(out);
+        if self.genome.sound_changes.is_empty() {
+            for _ in 0..self.syllables_per_name {
+                write_syllable(&self.genome, &table, &mut rng, &mut out)?;
             }
+            return Ok(());
         }
-        
-        syllable
-    }
-    
-    /// Choose a consonant for name generation.
-    fn choose_consonant(&self, rng: &mut SeededRng) -> String {
-        use crate::phonology::PhonemeCategory;
-        
-        let categories = self.genome.phoneme_inventory.available_categories();
-        if categories.is_empty() {
-            return String::new();
+
+        // Sound changes apply to the whole name
+        let mut name = String::new();
+        for _ in 0..self.syllables_per_name {
+            write_syllable(&self.genome, &table, &mut rng, &mut name)?;
         }
-        
-        let weights: Vec<f32> = categories
-            .iter()
-            .map(|cat| {
-                let idx = match cat {
-                    PhonemeCategory::Stops => 0,
-                    PhonemeCategory::Fricatives => 1,
-                    PhonemeCategory::Nasals => 2,
-                    PhonemeCategory::Liquids => 3,
-                    PhonemeCategory::Glides => 4,
-                };
-                self.genome.phoneme_inventory.category_weights[idx]
-            })
-            .collect();
-        
-        let category_idx = rng.weighted_choice(&weights);
-        let category = categories[category_idx];
-        
-        let consonants = self.genome.phoneme_inventory.get_category(category);
-        let consonant = rng.choice(consonants);
-        
-        consonant.0.clone()
+        out.write_str(&self.genome.apply_sound_changes(&name))
+    }
+
+    /// Number of different simple names of `syllables` syllables the language
+    /// can form, at most.
+    ///
+    /// Simple names use [`syllables_per_name`](Self::syllables_per_name)
+    /// syllables. Different syllables can spell the same name and sound changes
+    /// can merge names, so fewer may really be distinct.
+    pub fn name_space(&self, syllables: usize) -> u64 {
+        let per_syllable = self.table().syllable_count(&self.genome);
+        let syllables = u32::try_from(syllables).unwrap_or(u32::MAX);
+        per_syllable.saturating_pow(syllables)
+    }
+
+    /// The consonant tables for this system's genome.
+    pub(crate) fn table(&self) -> Arc<SyllableTable> {
+        self.table.get(&self.genome)
+    }
+
+    /// Generate the language's word for a concept with the cached tables.
+    pub(crate) fn word(&self, concept: &str) -> String {
+        generate_word_with(&self.genome, &self.table(), concept)
     }
     
     /// Generate a compound name from morphemes.
     pub fn generate_compound_name(&self, seed: u64, count: usize) -> String {
-        let mut rng = SeededRng::new(seed ^ self.genome.seed);
+        let mut rng = self.genome.algorithm_version.rng(seed ^ self.genome.seed);
         
         let morphemes: Vec<&str> = (0..count)
-            .map(|_| {
-                let m = self.morphemes.select_weighted(&mut rng, &self.geography);
-                m.form.as_str()
+            .filter_map(|_| {
+                let m = self.morphemes.select_weighted(&mut rng, &self.geography).ok()?;
+                Some(m.form.as_str())
             })
             .collect();
         
@@ -265,11 +333,105 @@ impl NamingSystem {
     }
 }
 
+/// A saved naming system, before the culture fills in what it lacks.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct NamingData {
+    genome: LinguisticGenome,
+    culture: CulturalProfile,
+    geography: Geography,
+    morphemes: MorphemeDatabase,
+    pattern: NamePattern,
+    combining_rule: CombiningRule,
+    syllables_per_name: usize,
+    // Naming systems saved by older releases lack these and use the culture's
+    #[serde(default)]
+    surname_inheritance: Option<SurnameInheritance>,
+    #[serde(default)]
+    gender_marking: Option<GenderMarking>,
+    #[serde(default)]
+    birth_order_naming: Option<BirthOrderNaming>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NamingSystem {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = NamingData::deserialize(deserializer)?;
+        let culture = &data.culture;
+        let version = data.genome.algorithm_version;
+        Ok(Self {
+            surname_inheritance: data
+                .surname_inheritance
+                .unwrap_or_else(|| SurnameInheritance::from_culture(culture)),
+            gender_marking: data
+                .gender_marking
+                .unwrap_or_else(|| GenderMarking::from_culture(culture)),
+            birth_order_naming: data
+                .birth_order_naming
+                .unwrap_or_else(|| BirthOrderNaming::from_culture_with_version(culture, version)),
+            genome: data.genome,
+            culture: data.culture,
+            geography: data.geography,
+            morphemes: data.morphemes,
+            pattern: data.pattern,
+            combining_rule: data.combining_rule,
+            syllables_per_name: data.syllables_per_name,
+            table: LazySyllableTable::default(),
+        })
+    }
+}
+
+impl HeapSize for NamingSystem {
+    fn heap_size(&self) -> usize {
+        self.genome.heap_size() + self.morphemes.heap_size() + self.table.heap_size()
+    }
+}
+
+/// Uppercases the first character written through it.
+struct Capitalized<'a, W> {
+    out: &'a mut W,
+    started: bool,
+}
+
+impl<'a, W: fmt::Write> Capitalized<'a, W> {
+    fn new(out: &'a mut W) -> Self {
+        Self {
+            out,
+            started: false,
+        }
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for Capitalized<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut chars = s.chars();
+        match chars.next() {
+            Some(first) if !self.started => {
+                self.started = true;
+                for upper in first.to_uppercase() {
+                    self.out.write_char(upper)?;
+                }
+                self.out.write_str(chars.as_str())
+            }
+            _ => self.out.write_str(s),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::morphology::MorphemeType;
 
+    /// A naming system for an average plains culture, with simple names.
+    pub(crate) fn plain_naming() -> NamingSystem {
+        let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 3.0, 3.0);
+        let genome = LinguisticGenome::from_culture(culture, Geography::Plains, 12345);
+        let mut naming = NamingSystem::new(genome, culture, Geography::Plains);
+        naming.pattern = NamePattern::Simple;
+        naming
+    }
+
     #[test]
     fn test_name_pattern_from_culture() {
         // High conscientiousness should give patronymic
@@ -283,6 +445,11 @@ mod tests {
         // Low honesty-humility should give elaborate
         let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 1.5, 3.0);
         assert_eq!(NamePattern::from_culture(&culture), NamePattern::Elaborate);
+
+        // Out-of-range scores behave like the nearest end of the scale
+        let high = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 5.0, 3.0);
+        let too_high = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 40.0, 3.0);
+        assert_eq!(NamePattern::from_culture(&too_high), NamePattern::from_culture(&high));
     }
 
     #[test]
@@ -312,6 +479,27 @@ mod tests {
         assert_ne!(name, name3);
     }
 
+    #[test]
+    fn test_simple_name_into_appends() {
+        let culture = CulturalProfile::new(4.0, 3.0, 3.0, 3.0, 3.0, 3.0);
+        let genome = LinguisticGenome::from_culture(culture, Geography::Coastal, 12345);
+        let naming = NamingSystem::new(genome, culture, Geography::Coastal);
+
+        let mut out = String::from("Hail, ");
+        naming.generate_simple_name_into(u64::MAX, &mut out).unwrap();
+        assert_eq!(out, format!("Hail, {}", naming.generate_simple_name(u64::MAX)));
+    }
+
+    #[test]
+    fn test_capitalized_skips_empty_writes() {
+        let mut out = String::new();
+        let mut capitalized = Capitalized::new(&mut out);
+        capitalized.write_str("").unwrap();
+        capitalized.write_str("ñam").unwrap();
+        capitalized.write_str("a").unwrap();
+        assert_eq!(out, "Ñama");
+    }
+
     #[test]
     fn test_compound_name_generation() {
         let culture = CulturalProfile::new(4.0, 4.0, 3.0, 3.0, 3.0, 3.0);
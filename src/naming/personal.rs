@@ -1,17 +1,40 @@
 //! Personal name generation: names for individuals.
 
+use super::birth_order::BirthOrderNaming;
+use super::gender::Gender;
 use super::{NamePattern, NamingSystem};
+use crate::drift::erode;
+use crate::genome::AlgorithmVersion;
+use crate::morphology::MorphemeType;
 use crate::seeded_rng::SeededRng;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 
 /// Context for generating a personal name.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PersonalNameContext {
     /// Unique entity ID
     pub entity_id: u64,
     /// Optional parent/father name for patronymic systems
     pub parent_name: Option<String>,
-    /// Birth order (for cultures that encode this)
+    /// Optional mother name for matronymic systems
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mother_name: Option<String>,
+    /// Names of the person's children, eldest first, for teknonymic systems
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub children: Vec<String>,
+    /// Birth order, 1 for the firstborn (for cultures that encode this)
     pub birth_order: Option<usize>,
+    /// Gender, for cultures whose names mark it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gender: Option<Gender>,
+    /// Family, clan or house name
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub surname: Option<String>,
 }
 
 impl PersonalNameContext {
@@ -20,7 +43,11 @@ impl PersonalNameContext {
         Self {
             entity_id,
             parent_name: None,
+            mother_name: None,
+            children: Vec::new(),
             birth_order: None,
+            gender: None,
+            surname: None,
         }
     }
     
@@ -29,9 +56,43 @@ impl PersonalNameContext {
         Self {
             entity_id,
             parent_name: Some(parent_name),
+            mother_name: None,
+            children: Vec::new(),
             birth_order: None,
+            gender: None,
+            surname: None,
         }
     }
+    
+    /// Name the person's mother, for matronymic systems.
+    pub fn with_mother(mut self, mother_name: String) -> Self {
+        self.mother_name = Some(mother_name);
+        self
+    }
+    
+    /// Add a child, for teknonymic systems. Add children eldest first.
+    pub fn with_child(mut self, child_name: String) -> Self {
+        self.children.push(child_name);
+        self
+    }
+    
+    /// Give the person's birth order, 1 for the firstborn.
+    pub fn with_birth_order(mut self, birth_order: usize) -> Self {
+        self.birth_order = Some(birth_order);
+        self
+    }
+    
+    /// Give the person's gender, for cultures whose names mark it.
+    pub fn with_gender(mut self, gender: Gender) -> Self {
+        self.gender = Some(gender);
+        self
+    }
+    
+    /// Give the person a family name.
+    pub fn with_surname(mut self, surname: String) -> Self {
+        self.surname = Some(surname);
+        self
+    }
 }
 
 impl NamingSystem {
@@ -39,16 +100,26 @@ impl NamingSystem {
     pub fn generate_personal_name(&self, context: &PersonalNameContext) -> String {
         match self.pattern {
             NamePattern::Simple => {
-                self.generate_simple_name(context.entity_id)
+                self.given_name(context)
             }
             NamePattern::Patronymic => {
                 self.generate_patronymic_name(context)
             }
+            NamePattern::Matronymic => {
+                self.generate_matronymic_name(context)
+            }
+            NamePattern::Bilateral => {
+                self.generate_bilateral_name(context)
+            }
+            NamePattern::Teknonymic => {
+                self.generate_teknonymic_name(context)
+            }
             NamePattern::Compound => {
                 // Use 2-3 morphemes for personal compound names
-                let mut rng = SeededRng::new(context.entity_id ^ self.genome.seed);
+                let mut rng = self.genome.algorithm_version.rng(context.entity_id ^ self.genome.seed);
                 let count = 2 + rng.range(0, 2);
-                self.generate_compound_name(context.entity_id, count)
+                let name = self.generate_compound_name(context.entity_id, count);
+                self.mark_birth_order(self.mark_gender(&name, context.gender), context)
             }
             NamePattern::Elaborate => {
                 self.generate_elaborate_name(context)
@@ -61,10 +132,10 @@ impl NamingSystem {
     
     /// Generate a patronymic name (e.g., "Aran Thorson").
     fn generate_patronymic_name(&self, context: &PersonalNameContext) -> String {
-        let given_name = self.generate_simple_name(context.entity_id);
+        let given_name = self.given_name(context);
         
         if let Some(parent) = &context.parent_name {
-            let patronymic = self.create_patronymic(parent);
+            let patronymic = self.create_patronymic(parent, context);
             format!("{} {}", given_name, patronymic)
         } else {
             // No parent name provided, just use given name
@@ -72,8 +143,109 @@ impl NamingSystem {
         }
     }
     
+    /// Generate a matronymic name (e.g., "Aran Meludottir").
+    fn generate_matronymic_name(&self, context: &PersonalNameContext) -> String {
+        let given_name = self.given_name(context);
+        
+        match &context.mother_name {
+            Some(mother) => format!("{} {}", given_name, self.create_lineage_name(mother, context)),
+            None => given_name,
+        }
+    }
+    
+    /// Generate a name after both parents (e.g., "Aran Thorson Meludottir").
+    fn generate_bilateral_name(&self, context: &PersonalNameContext) -> String {
+        let mut name = self.given_name(context);
+        
+        for parent in context.parent_name.iter().chain(&context.mother_name) {
+            name.push(' ');
+            name.push_str(&self.create_lineage_name(parent, context));
+        }
+        name
+    }
+    
+    /// Generate a teknonymic name (e.g., "Aran, father of Thorin").
+    fn generate_teknonymic_name(&self, context: &PersonalNameContext) -> String {
+        let given_name = self.given_name(context);
+        
+        // Parents are known by their eldest child
+        match context.children.first() {
+            Some(child) => {
+                let kin = self.gendered_kin(
+                    context.gender,
+                    MorphemeType::Mother,
+                    MorphemeType::Father,
+                    MorphemeType::Parent,
+                );
+                let parent = Self::capitalize_first_letter(&self.kinship_word(kin));
+                format!("{} {} {}", given_name, parent, child)
+            }
+            None => given_name,
+        }
+    }
+    
+    /// Get the language's word for a kinship term.
+    pub fn kinship_word(&self, kin: MorphemeType) -> String {
+        self.word(kin.as_str())
+    }
+    
+    /// Get the affix marking descent that a kinship word wore down to, as
+    /// Icelandic *-son* comes from the word for "son".
+    ///
+    /// Words for "son" and "daughter", or "father" and "mother", that would wear
+    /// down alike keep their whole form, so the genders stay apart.
+    pub fn lineage_affix(&self, kin: MorphemeType) -> String {
+        let word = self.kinship_word(kin);
+        let affix = erode(&self.genome, &word);
+        let counterpart = match kin {
+            MorphemeType::Son => MorphemeType::Daughter,
+            MorphemeType::Daughter => MorphemeType::Son,
+            MorphemeType::Father => MorphemeType::Mother,
+            MorphemeType::Mother => MorphemeType::Father,
+            _ => return affix,
+        };
+        
+        if erode(&self.genome, &self.kinship_word(counterpart)) == affix {
+            word
+        } else {
+            affix
+        }
+    }
+    
+    /// Generate a person's given name, marked for gender and birth order if
+    /// the culture does.
+    pub(crate) fn given_name(&self, context: &PersonalNameContext) -> String {
+        let name = self.generate_simple_name(context.entity_id);
+        self.mark_birth_order(self.mark_gender(&name, context.gender), context)
+    }
+    
+    /// Create a lineage name from a parent's name (e.g., "Thorson").
+    ///
+    /// The affix comes from the word for "son" or "daughter" in cultures that
+    /// mark gender, and from the word for "child" otherwise.
+    fn create_lineage_name(&self, parent_name: &str, context: &PersonalNameContext) -> String {
+        let kin = self.gendered_kin(
+            context.gender,
+            MorphemeType::Daughter,
+            MorphemeType::Son,
+            MorphemeType::Child,
+        );
+        let affix = self.lineage_affix(kin);
+        
+        // High conscientiousness = hyphenated
+        if self.culture.normalized_conscientiousness() > 0.6 {
+            format!("{}-{}", parent_name, affix)
+        } else {
+            format!("{}{}", parent_name, affix)
+        }
+    }
+    
     /// Create a patronymic form from a parent's name.
-    fn create_patronymic(&self, parent_name: &str) -> String {
+    fn create_patronymic(&self, parent_name: &str, context: &PersonalNameContext) -> String {
+        if self.genome.algorithm_version >= AlgorithmVersion::V4 {
+            return self.create_lineage_name(parent_name, context);
+        }
+        
         // Generate a suffix based on the language
         let suffix_seed = self.genome.seed ^ 0x504154524F4E594D; // "PATRONYM" in hex
         let suffix = self.generate_simple_name(suffix_seed);
@@ -91,24 +263,24 @@ impl NamingSystem {
     
     /// Generate an elaborate name with titles.
     fn generate_elaborate_name(&self, context: &PersonalNameContext) -> String {
-        let mut rng = SeededRng::new(context.entity_id ^ self.genome.seed);
+        let mut rng = self.genome.algorithm_version.rng(context.entity_id ^ self.genome.seed);
         
         // Title
         let title = self.generate_title(&mut rng);
         
         // Given name
-        let given_name = self.generate_simple_name(context.entity_id);
+        let given_name = self.given_name(context);
         
-        // Lineage/ordinal
-        let lineage = self.generate_lineage(&mut rng);
+        // Lineage/ordinal; names that already show birth order take no ordinal
+        let shows_birth_order =
+            context.birth_order.is_some() && self.birth_order_naming != BirthOrderNaming::Ignored;
+        let lineage = self.generate_lineage(&mut rng, !shows_birth_order);
         
         format!("{} {} {}", title, given_name, lineage)
     }
     
     /// Generate a title (Lord, Lady, etc. but in the language).
     fn generate_title(&self, rng: &mut SeededRng) -> String {
-        use crate::morphology::MorphemeType;
-        
         // Select from power/authority morphemes
         let title_types = [
             MorphemeType::Power,
@@ -126,11 +298,9 @@ impl NamingSystem {
     }
     
     /// Generate a lineage suffix (e.g., "the Third", "of the Mountains").
-    fn generate_lineage(&self, rng: &mut SeededRng) -> String {
-        use crate::morphology::MorphemeType;
-        
+    fn generate_lineage(&self, rng: &mut SeededRng, ordinals: bool) -> String {
         // 50% chance of ordinal, 50% chance of geographic
-        if rng.next() < 0.5 {
+        if rng.next() < 0.5 && ordinals {
             // Ordinal
             let ordinals = ["First", "Second", "Third", "Fourth", "Fifth"];
             let idx = rng.range(0, ordinals.len());
@@ -154,9 +324,9 @@ impl NamingSystem {
     
     /// Generate a descriptive name (name + characteristic).
     fn generate_descriptive_name(&self, context: &PersonalNameContext) -> String {
-        let mut rng = SeededRng::new(context.entity_id ^ self.genome.seed);
+        let mut rng = self.genome.algorithm_version.rng(context.entity_id ^ self.genome.seed);
         
-        let given_name = self.generate_simple_name(context.entity_id);
+        let given_name = self.given_name(context);
         let characteristic = self.generate_characteristic(&mut rng);
         
         // Format depends on combining rule
@@ -172,8 +342,6 @@ impl NamingSystem {
     
     /// Generate a characteristic descriptor.
     fn generate_characteristic(&self, rng: &mut SeededRng) -> String {
-        use crate::morphology::MorphemeType;
-        
         let characteristic_types = [
             MorphemeType::Strong,
             MorphemeType::Wise,
@@ -269,5 +437,85 @@ mod tests {
         
         assert_ne!(name1, name2);
     }
+
+    #[test]
+    fn test_lineage_names() {
+        let culture = CulturalProfile::new(3.0, 3.0, 4.5, 3.0, 3.0, 3.0);
+        let genome = LinguisticGenome::from_culture(culture, Geography::Plains, 12345);
+        let mut naming = NamingSystem::new(genome, culture, Geography::Plains);
+        let context = PersonalNameContext::with_parent(42, "Thorin".to_string())
+            .with_mother("Melu".to_string());
+        let given = naming.generate_simple_name(42);
+        
+        // The lineage affix is worn down from the word for "child"
+        let affix = naming.lineage_affix(MorphemeType::Child);
+        assert!(naming.kinship_word(MorphemeType::Child).starts_with(&affix));
+        assert_eq!(
+            naming.kinship_word(MorphemeType::Child),
+            crate::generation::generate_word(&naming.genome, "child")
+        );
+        
+        let patronymic = naming.generate_personal_name(&context);
+        assert_eq!(patronymic, format!("{} Thorin-{}", given, affix));
+        
+        naming.pattern = NamePattern::Matronymic;
+        let matronymic = naming.generate_personal_name(&context);
+        assert_eq!(matronymic, format!("{} Melu-{}", given, affix));
+        
+        naming.pattern = NamePattern::Bilateral;
+        assert_eq!(
+            naming.generate_personal_name(&context),
+            format!("{} Thorin-{} Melu-{}", given, affix, affix)
+        );
+    }
+
+    #[test]
+    fn test_teknonymic_name() {
+        let culture = CulturalProfile::new(4.5, 3.0, 3.0, 3.0, 3.0, 4.5);
+        let genome = LinguisticGenome::from_culture(culture, Geography::Forest, 12345);
+        let naming = NamingSystem::new(genome, culture, Geography::Forest);
+        assert_eq!(naming.pattern, NamePattern::Teknonymic);
+        
+        let childless = PersonalNameContext::simple(42);
+        let given = naming.generate_personal_name(&childless);
+        let parent = childless.with_child("Thorin".to_string()).with_child("Melu".to_string());
+        let name = naming.generate_personal_name(&parent);
+        
+        assert!(name.starts_with(&given));
+        assert!(name.ends_with(" Thorin"));
+    }
+
+    #[test]
+    fn test_older_versions_keep_their_names() {
+        let culture = CulturalProfile::new(4.5, 3.0, 4.5, 3.0, 3.0, 4.0);
+        let v3 = LinguisticGenome::from_culture_with_version(
+            culture, Geography::Plains, 7, AlgorithmVersion::V3,
+        );
+        let v4 = LinguisticGenome::from_culture_with_version(
+            culture, Geography::Plains, 7, AlgorithmVersion::V4,
+        );
+        
+        assert_eq!(
+            NamePattern::from_culture_with_version(&culture, AlgorithmVersion::V3),
+            NamePattern::Patronymic
+        );
+        assert_eq!(
+            NamePattern::from_culture_with_version(&culture, AlgorithmVersion::V4),
+            NamePattern::Matronymic
+        );
+        
+        // Version 3 patronymics keep the marker cut from a random name
+        let naming = NamingSystem::new(v3, culture, Geography::Plains);
+        let marker: String = naming
+            .generate_simple_name(naming.genome.seed ^ 0x504154524F4E594D)
+            .chars()
+            .take(3)
+            .collect();
+        let context = PersonalNameContext::with_parent(1, "Thorin".to_string());
+        let name = naming.generate_personal_name(&context);
+        assert!(name.ends_with(&format!("Thorin-{}", marker)));
+        let naming = NamingSystem::new(v4, culture, Geography::Plains);
+        assert_eq!(naming.pattern, NamePattern::Matronymic);
+    }
 }
 
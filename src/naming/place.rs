@@ -4,9 +4,11 @@ use super::NamingSystem;
 use crate::culture::Geography;
 use crate::morphology::MorphemeType;
 use crate::seeded_rng::SeededRng;
+use alloc::{format, string::String, vec};
 
 /// The type of place being named.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlaceType {
     /// Settlement (village, town, city)
     Settlement,
@@ -20,6 +22,7 @@ pub enum PlaceType {
 
 /// Context for generating a place name.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlaceNameContext {
     /// Unique place ID
     pub place_id: u64,
@@ -80,7 +83,7 @@ enum PlaceNamingStrategy {
 impl NamingSystem {
     /// Generate a complete place name based on cultural patterns and context.
     pub fn generate_place_name(&self, context: &PlaceNameContext) -> String {
-        let mut rng = SeededRng::new(context.place_id ^ self.genome.seed);
+        let mut rng = self.genome.algorithm_version.rng(context.place_id ^ self.genome.seed);
         
         // Determine naming strategy based on culture and available context
         let strategy = self.determine_place_strategy(context, &mut rng);
@@ -0,0 +1,259 @@
+//! Unique names within a population.
+//!
+//! Names are generated from entity IDs, so two entities can draw the same name.
+//! A [`NameRegistry`] remembers the names handed out in one scope, such as a
+//! settlement, a family or a whole world, and re-rolls a name deterministically
+//! until it finds one nobody in the scope has.
+
+use super::personal::PersonalNameContext;
+use super::NamingSystem;
+use crate::error::Error;
+use crate::seeded_rng::stable_hash;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+
+/// Key for the seeds of re-rolled names: "reroll!" in ASCII.
+const REROLL_KEY: u64 = 0x0072_6572_6f6c_6c21;
+
+/// The names taken in one scope.
+///
+/// Asking for the name of an ID the registry has already named returns the same
+/// name. Otherwise the first name not yet taken is handed out: the entity's own
+/// name if it is free, then names from re-rolled seeds. Re-rolls depend only on
+/// the ID and the names already taken, so registering the same entities in the
+/// same order always gives the same names.
+///
+/// Names are compared ignoring case.
+///
+/// # Example
+///
+/// ```
+/// use phyla_lang::{CulturalProfile, Geography, Language, NameRegistry};
+///
+/// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+/// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+///
+/// let mut town = NameRegistry::new();
+/// town.reserve("Aru");
+/// let names: Vec<String> = (0..100)
+///     .map(|id| town.simple_name(&language.naming, id).unwrap())
+///     .collect();
+/// assert!(!names.iter().any(|name| name == "Aru"));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NameRegistry {
+    /// Lowercased names no one else may be given, reserved ones included
+    taken: BTreeSet<String>,
+    /// Names handed out, by entity ID
+    assigned: BTreeMap<u64, String>,
+    /// Most names tried for one entity
+    max_attempts: u32,
+}
+
+impl NameRegistry {
+    /// Names tried for one entity by default.
+    pub const DEFAULT_MAX_ATTEMPTS: u32 = 100;
+
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            taken: BTreeSet::new(),
+            assigned: BTreeMap::new(),
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Try at most `max_attempts` names for an entity before giving up.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Keep a name from being handed out, e.g. one a player chose.
+    ///
+    /// Returns false if the name was already taken.
+    pub fn reserve(&mut self, name: &str) -> bool {
+        self.taken.insert(name.to_lowercase())
+    }
+
+    /// Check whether a name is taken, ignoring case.
+    pub fn is_taken(&self, name: &str) -> bool {
+        self.taken.contains(&name.to_lowercase())
+    }
+
+    /// Get the name handed out to an entity.
+    pub fn name_of(&self, entity_id: u64) -> Option<&str> {
+        self.assigned.get(&entity_id).map(String::as_str)
+    }
+
+    /// Free an entity's name so it can be handed out again.
+    pub fn release(&mut self, entity_id: u64) -> Option<String> {
+        let name = self.assigned.remove(&entity_id)?;
+        self.taken.remove(&name.to_lowercase());
+        Some(name)
+    }
+
+    /// Number of names taken, reserved ones included.
+    pub fn len(&self) -> usize {
+        self.taken.len()
+    }
+
+    /// Check whether no names are taken.
+    pub fn is_empty(&self) -> bool {
+        self.taken.is_empty()
+    }
+
+    /// Give an entity a unique simple name.
+    ///
+    /// Returns [`Error::NamesExhausted`] if every name tried is taken.
+    pub fn simple_name(&mut self, naming: &NamingSystem, entity_id: u64) -> Result<String, Error> {
+        self.register_with(entity_id, |seed| naming.generate_simple_name(seed))
+    }
+
+    /// Give an entity a unique personal name.
+    ///
+    /// Re-rolls keep the rest of the context, such as the parent's name.
+    pub fn personal_name(
+        &mut self,
+        naming: &NamingSystem,
+        context: &PersonalNameContext,
+    ) -> Result<String, Error> {
+        let mut context = context.clone();
+        self.register_with(context.entity_id, |seed| {
+            context.entity_id = seed;
+            naming.generate_personal_name(&context)
+        })
+    }
+
+    /// Give an entity a unique name from any generator of names from seeds.
+    ///
+    /// The generator is called with the entity ID first, then with re-rolled
+    /// seeds until it returns a name that is not taken.
+    pub fn register_with<F>(&mut self, entity_id: u64, mut generate: F) -> Result<String, Error>
+    where
+        F: FnMut(u64) -> String,
+    {
+        if let Some(name) = self.assigned.get(&entity_id) {
+            return Ok(name.clone());
+        }
+
+        for attempt in 0..self.max_attempts {
+            let name = generate(reroll_seed(entity_id, attempt));
+            // A generator that cannot make names makes no unique ones either
+            if !name.is_empty() && self.taken.insert(name.to_lowercase()) {
+                self.assigned.insert(entity_id, name.clone());
+                return Ok(name);
+            }
+        }
+        Err(Error::NamesExhausted {
+            attempts: self.max_attempts,
+        })
+    }
+
+    /// Estimate how many more simple names of `syllables` syllables could be
+    /// handed out.
+    ///
+    /// This is the naming system's [name space](NamingSystem::name_space) less
+    /// the names already taken. Since the name space is an upper bound and
+    /// re-rolling gives up after a few attempts, registration starts failing
+    /// well before this reaches zero.
+    pub fn remaining_capacity(&self, naming: &NamingSystem, syllables: usize) -> u64 {
+        naming
+            .name_space(syllables)
+            .saturating_sub(self.taken.len() as u64)
+    }
+}
+
+impl Default for NameRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The seed for an entity's name on a given attempt: the ID itself first.
+fn reroll_seed(entity_id: u64, attempt: u32) -> u64 {
+    if attempt == 0 {
+        entity_id
+    } else {
+        stable_hash(&format!("{}/{}", entity_id, attempt), REROLL_KEY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::culture::{CulturalProfile, Geography};
+    use crate::genome::LinguisticGenome;
+
+    fn naming() -> NamingSystem {
+        // Mountain names are short, so collisions come quickly
+        let culture = CulturalProfile::new(3.0, 2.0, 3.0, 3.0, 3.0, 3.0);
+        let genome = LinguisticGenome::from_culture(culture, Geography::Mountains, 7);
+        NamingSystem::new(genome, culture, Geography::Mountains)
+    }
+
+    #[test]
+    fn test_names_are_unique_and_stable() {
+        let naming = naming();
+        let mut registry = NameRegistry::new();
+        let names: Vec<String> = (0..500)
+            .map(|id| registry.simple_name(&naming, id).unwrap())
+            .collect();
+
+        let distinct: BTreeSet<String> = names.iter().map(|n| n.to_lowercase()).collect();
+        assert_eq!(distinct.len(), names.len());
+        assert_eq!(registry.simple_name(&naming, 42).unwrap(), names[42]);
+
+        // The same registrations in the same order give the same names
+        let mut again = NameRegistry::new();
+        for (id, name) in names.iter().enumerate() {
+            assert_eq!(&again.simple_name(&naming, id as u64).unwrap(), name);
+        }
+    }
+
+    #[test]
+    fn test_free_names_are_kept() {
+        let naming = naming();
+        let mut registry = NameRegistry::new();
+        let name = registry.simple_name(&naming, 9).unwrap();
+        assert_eq!(name, naming.generate_simple_name(9));
+        assert_eq!(registry.name_of(9), Some(name.as_str()));
+    }
+
+    #[test]
+    fn test_reserved_and_released_names() {
+        let naming = naming();
+        let reserved = naming.generate_simple_name(3);
+        let mut registry = NameRegistry::new();
+        assert!(registry.reserve(&reserved.to_uppercase()));
+        assert!(!registry.reserve(&reserved));
+
+        let name = registry.simple_name(&naming, 3).unwrap();
+        assert_ne!(name, reserved);
+        assert_eq!(registry.release(3), Some(name.clone()));
+        assert!(!registry.is_taken(&name));
+        assert!(registry.is_taken(&reserved));
+    }
+
+    #[test]
+    fn test_exhaustion_and_capacity() {
+        let naming = naming();
+        let mut registry = NameRegistry::new().with_max_attempts(3);
+        let result = registry.register_with(1, |_| "Same".into());
+        assert!(result.is_ok());
+        assert!(matches!(
+            registry.register_with(2, |_| "Same".into()),
+            Err(Error::NamesExhausted { attempts: 3 })
+        ));
+
+        let space = naming.name_space(naming.syllables_per_name);
+        assert!(space > 0);
+        assert_eq!(
+            registry.remaining_capacity(&naming, naming.syllables_per_name),
+            space - 1
+        );
+        assert!(naming.name_space(3) > naming.name_space(2));
+    }
+}
@@ -0,0 +1,309 @@
+//! Family names: surnames, clan and house names, and how children inherit them.
+
+use super::personal::PersonalNameContext;
+use super::NamingSystem;
+use crate::culture::CulturalProfile;
+use crate::morphology::{CombiningRule, MorphemeType};
+use alloc::format;
+use alloc::string::String;
+
+/// Totems clans and houses are named after.
+const TOTEMS: &[MorphemeType] = &[
+    MorphemeType::Stone,
+    MorphemeType::Storm,
+    MorphemeType::Moon,
+    MorphemeType::Sun,
+    MorphemeType::Star,
+    MorphemeType::Fire,
+    MorphemeType::River,
+    MorphemeType::Mountain,
+    MorphemeType::Forest,
+    MorphemeType::Sea,
+    MorphemeType::Sky,
+];
+
+/// How children get their family name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SurnameInheritance {
+    /// The father's surname (e.g., "Aran Thorsk", son of "Beno Thorsk")
+    #[default]
+    Patrilineal,
+    /// The mother's surname
+    Matrilineal,
+    /// Both: the first part of the father's surname, then of the mother's
+    /// (e.g., "Aran Thorsk Melu")
+    Bilineal,
+    /// Clan or house names, named after a founder or a totem and passed down
+    /// the father's line, or the mother's if the father has none
+    Clan,
+}
+
+impl SurnameInheritance {
+    /// Determine how surnames are inherited from cultural traits.
+    pub fn from_culture(culture: &CulturalProfile) -> Self {
+        // Low honesty-humility = status-conscious great houses
+        if culture.normalized_honesty_humility() < 0.375 {
+            return SurnameInheritance::Clan;
+        }
+
+        // High openness = both lines are kept
+        if culture.normalized_openness() > 0.7 {
+            return SurnameInheritance::Bilineal;
+        }
+
+        // Cooperative, emotionally expressive cultures trace descent through mothers
+        if culture.normalized_agreeableness() > 0.7 && culture.normalized_emotionality() > 0.6 {
+            return SurnameInheritance::Matrilineal;
+        }
+
+        SurnameInheritance::Patrilineal
+    }
+}
+
+/// What a clan or house is named after.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HouseOrigin {
+    /// The name of the founder (e.g., "House of Aran")
+    Founder(String),
+    /// A totem (e.g., the Storm clan)
+    Totem(MorphemeType),
+}
+
+impl NamingSystem {
+    /// Generate the surname of a new family.
+    ///
+    /// In clan cultures this is a house named after a totem; otherwise it is a
+    /// name built from the language's sounds.
+    pub fn generate_surname(&self, family_id: u64) -> String {
+        if self.surname_inheritance == SurnameInheritance::Clan {
+            let totem = self.clan_totem(family_id);
+            return self.generate_house_name(&HouseOrigin::Totem(totem));
+        }
+
+        let concept = format!("surname_{}", family_id);
+        let seed = self
+            .genome
+            .algorithm_version
+            .hash(&concept, self.genome.seed);
+        self.generate_simple_name(seed)
+    }
+
+    /// Choose the totem of a clan, favouring those important to the culture.
+    pub fn clan_totem(&self, clan_id: u64) -> MorphemeType {
+        let mut rng = self
+            .genome
+            .algorithm_version
+            .rng(clan_id ^ self.genome.seed ^ 0x544F54454D); // "TOTEM"
+        self.morphemes
+            .select_from_types(TOTEMS, &mut rng)
+            .map_or(MorphemeType::Stone, |morpheme| morpheme.meaning)
+    }
+
+    /// Generate the name of a clan or house.
+    ///
+    /// The language's word for "clan" joins the founder's name or the totem
+    /// following the culture's combining rule.
+    pub fn generate_house_name(&self, origin: &HouseOrigin) -> String {
+        let root = match origin {
+            HouseOrigin::Founder(name) => name.clone(),
+            HouseOrigin::Totem(totem) => match self.morphemes.get(totem) {
+                Some(morpheme) => Self::capitalize_first_letter(&morpheme.form),
+                None => self.generate_simple_name(*totem as u64),
+            },
+        };
+        let clan = self.word("clan");
+
+        match self.combining_rule {
+            // "Clan of Aran" reads as a name only when the clan word leads
+            CombiningRule::Genitive => self
+                .combining_rule
+                .combine(&root, &Self::capitalize_first_letter(&clan)),
+            rule => rule.combine(&root, &clan),
+        }
+    }
+
+    /// Get the surname a child of these parents inherits.
+    ///
+    /// A parent without a surname founds a family, named by
+    /// [`generate_surname`](Self::generate_surname) from their entity ID.
+    pub fn inherit_surname(
+        &self,
+        father: &PersonalNameContext,
+        mother: &PersonalNameContext,
+    ) -> String {
+        match self.surname_inheritance {
+            SurnameInheritance::Patrilineal => self.family_surname(father),
+            SurnameInheritance::Matrilineal => self.family_surname(mother),
+            SurnameInheritance::Bilineal => {
+                let paternal = self.family_surname(father);
+                let maternal = self.family_surname(mother);
+                format!("{} {}", first_part(&paternal), first_part(&maternal))
+            }
+            SurnameInheritance::Clan => match (&father.surname, &mother.surname) {
+                (None, Some(surname)) => surname.clone(),
+                _ => self.family_surname(father),
+            },
+        }
+    }
+
+    /// Build the naming context of a child of these parents.
+    ///
+    /// The child gets the inherited surname, and the parents' given names, as
+    /// marked for their gender and birth order, for cultures that name children
+    /// after their parents.
+    pub fn child_context(
+        &self,
+        child_id: u64,
+        father: &PersonalNameContext,
+        mother: &PersonalNameContext,
+    ) -> PersonalNameContext {
+        let father_name = self.given_name(father);
+        let mother_name = self.given_name(mother);
+        PersonalNameContext::with_parent(child_id, father_name)
+            .with_mother(mother_name)
+            .with_surname(self.inherit_surname(father, mother))
+    }
+
+    /// Generate a complete name: the personal name, then the surname if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phyla_lang::{CulturalProfile, Geography, Language, PersonalNameContext};
+    ///
+    /// let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    /// let language = Language::from_culture(culture, Geography::Coastal, 12345);
+    /// let naming = &language.naming;
+    ///
+    /// let father = PersonalNameContext::simple(1);
+    /// let mother = PersonalNameContext::simple(2).with_surname(naming.generate_surname(77));
+    /// let child = naming.child_context(3, &father, &mother);
+    /// println!("{}", naming.generate_full_name(&child));
+    /// ```
+    pub fn generate_full_name(&self, context: &PersonalNameContext) -> String {
+        let name = self.generate_personal_name(context);
+        match &context.surname {
+            Some(surname) => format!("{} {}", name, surname),
+            None => name,
+        }
+    }
+
+    /// A person's surname, founding a family if they have none.
+    fn family_surname(&self, person: &PersonalNameContext) -> String {
+        match &person.surname {
+            Some(surname) => surname.clone(),
+            None => self.generate_surname(person.entity_id),
+        }
+    }
+}
+
+/// The first word of a surname: the paternal part of a double surname.
+fn first_part(surname: &str) -> &str {
+    surname.split_whitespace().next().unwrap_or(surname)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::naming::gender::{Gender, GenderMarking};
+    use crate::naming::tests::plain_naming;
+
+    fn naming(surname_inheritance: SurnameInheritance) -> NamingSystem {
+        NamingSystem {
+            surname_inheritance,
+            ..plain_naming()
+        }
+    }
+
+    fn parents() -> (PersonalNameContext, PersonalNameContext) {
+        (
+            PersonalNameContext::simple(1).with_surname("Thorsk".into()),
+            PersonalNameContext::simple(2).with_surname("Melu Aven".into()),
+        )
+    }
+
+    #[test]
+    fn test_inheritance_modes() {
+        let (father, mother) = parents();
+        let surname = |mode| naming(mode).inherit_surname(&father, &mother);
+
+        assert_eq!(surname(SurnameInheritance::Patrilineal), "Thorsk");
+        assert_eq!(surname(SurnameInheritance::Matrilineal), "Melu Aven");
+        assert_eq!(surname(SurnameInheritance::Bilineal), "Thorsk Melu");
+        assert_eq!(surname(SurnameInheritance::Clan), "Thorsk");
+    }
+
+    #[test]
+    fn test_founders_start_families() {
+        let naming = naming(SurnameInheritance::Patrilineal);
+        let founder = PersonalNameContext::simple(10);
+        let mother = PersonalNameContext::simple(11);
+
+        let first = naming.child_context(20, &founder, &mother);
+        let second = naming.child_context(21, &founder, &mother);
+        assert_eq!(first.surname, Some(naming.generate_surname(10)));
+        assert_eq!(first.surname, second.surname);
+
+        // Grandchildren keep the name
+        let grandchild = naming.child_context(30, &first, &PersonalNameContext::simple(12));
+        assert_eq!(grandchild.surname, first.surname);
+        assert!(naming
+            .generate_full_name(&grandchild)
+            .ends_with(first.surname.as_deref().unwrap()));
+    }
+
+    #[test]
+    fn test_children_take_parents_given_names() {
+        let mut naming = naming(SurnameInheritance::Patrilineal);
+        naming.gender_marking = GenderMarking::Endings;
+        let father = PersonalNameContext::simple(1).with_gender(Gender::Male);
+        let mother = PersonalNameContext::simple(2).with_gender(Gender::Female);
+
+        let child = naming.child_context(3, &father, &mother);
+        assert_eq!(
+            child.parent_name,
+            Some(naming.generate_personal_name(&father))
+        );
+        assert_eq!(
+            child.mother_name,
+            Some(naming.generate_personal_name(&mother))
+        );
+        assert_ne!(child.parent_name, Some(naming.generate_simple_name(1)));
+    }
+
+    #[test]
+    fn test_house_names() {
+        let naming = naming(SurnameInheritance::Clan);
+        let totem = naming.clan_totem(5);
+        assert!(TOTEMS.contains(&totem));
+
+        let house = naming.generate_surname(5);
+        assert_eq!(
+            house,
+            naming.generate_house_name(&HouseOrigin::Totem(totem))
+        );
+        let founded = naming.generate_house_name(&HouseOrigin::Founder("Aran".into()));
+        assert!(founded.contains("Aran"));
+    }
+
+    #[test]
+    fn test_inheritance_from_culture() {
+        let status = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 1.5, 3.0);
+        assert_eq!(
+            SurnameInheritance::from_culture(&status),
+            SurnameInheritance::Clan
+        );
+        let open = CulturalProfile::new(3.0, 4.5, 3.0, 3.0, 3.0, 3.0);
+        assert_eq!(
+            SurnameInheritance::from_culture(&open),
+            SurnameInheritance::Bilineal
+        );
+        let plain = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 3.0, 3.0);
+        assert_eq!(
+            SurnameInheritance::from_culture(&plain),
+            SurnameInheritance::Patrilineal
+        );
+    }
+}
@@ -1,8 +1,14 @@
 //! Phonological components: phonemes, syllable structures, and constraints.
 
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 
 /// A consonant sound.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Consonant(pub String);
 
 impl Consonant {
@@ -13,6 +19,7 @@ impl Consonant {
 
 /// A vowel sound.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vowel(pub String);
 
 impl Vowel {
@@ -23,6 +30,7 @@ impl Vowel {
 
 /// Categories of consonants based on manner and place of articulation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PhonemeCategory {
     Stops,
     Fricatives,
@@ -33,6 +41,7 @@ pub enum PhonemeCategory {
 
 /// The complete inventory of sounds available in a language.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhonemeInventory {
     pub stops: Vec<Consonant>,
     pub fricatives: Vec<Consonant>,
@@ -69,6 +78,46 @@ impl PhonemeInventory {
         }
     }
 
+    /// Check whether a phoneme belongs to this inventory.
+    pub fn contains(&self, phoneme: &str) -> bool {
+        self.vowels.iter().any(|v| v.0 == phoneme)
+            || self.all_consonants().iter().any(|c| c.0 == phoneme)
+    }
+
+    /// Split a word into phonemes of this inventory.
+    ///
+    /// Uses longest match, so multi-character phonemes like "kʼ" stay whole.
+    /// Characters that match no phoneme become single-character segments.
+    pub fn segment<'a>(&self, word: &'a str) -> Vec<&'a str> {
+        self.segment_with(word, &[])
+    }
+
+    /// Split a word into phonemes of this inventory plus some extra phonemes.
+    pub fn segment_with<'a>(&self, word: &'a str, extra: &[&str]) -> Vec<&'a str> {
+        let mut known: Vec<&str> = self
+            .all_consonants()
+            .into_iter()
+            .map(|c| c.0.as_str())
+            .chain(self.vowels.iter().map(|v| v.0.as_str()))
+            .chain(extra.iter().copied())
+            .filter(|p| !p.is_empty())
+            .collect();
+        // Longest phonemes first so that "kʼ" wins over "k"
+        known.sort_by_key(|p| core::cmp::Reverse(p.len()));
+
+        let mut segments = Vec::new();
+        let mut rest = word;
+        while let Some(first) = rest.chars().next() {
+            let len = known
+                .iter()
+                .find(|p| rest.starts_with(**p))
+                .map_or(first.len_utf8(), |p| p.len());
+            segments.push(&rest[..len]);
+            rest = &rest[len..];
+        }
+        segments
+    }
+
     /// Get the categories that have consonants.
     pub fn available_categories(&self) -> Vec<PhonemeCategory> {
         let mut categories = Vec::new();
@@ -91,8 +140,155 @@ impl PhonemeInventory {
     }
 }
 
+/// Check whether a phoneme symbol is a vowel.
+pub fn is_vowel_symbol(phoneme: &str) -> bool {
+    phoneme
+        .chars()
+        .next()
+        .is_some_and(|c| "aeiouəɛɔæɨɯyøœɑɒʊɪ".contains(c))
+}
+
+/// Articulatory features of a phoneme, used to measure how similar two sounds are.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhonemeFeatures {
+    /// A consonant: manner, place (0 = lips ... 7 = glottis) and voicing
+    Consonant {
+        manner: Manner,
+        place: u8,
+        voiced: bool,
+    },
+    /// A vowel: height (0 = low ... 2 = high), backness (0 = front ... 2 = back), rounding
+    Vowel {
+        height: u8,
+        backness: u8,
+        rounded: bool,
+    },
+}
+
+/// Manner of articulation of a consonant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Manner {
+    Stop,
+    Ejective,
+    Fricative,
+    Nasal,
+    Liquid,
+    Glide,
+}
+
+impl PhonemeFeatures {
+    /// Look up the features of a phoneme symbol, if it is a known sound.
+    pub fn of(phoneme: &str) -> Option<Self> {
+        use Manner::*;
+
+        let consonant = |manner, place, voiced| {
+            Some(Self::Consonant {
+                manner,
+                place,
+                voiced,
+            })
+        };
+        let vowel = |height, backness, rounded| {
+            Some(Self::Vowel {
+                height,
+                backness,
+                rounded,
+            })
+        };
+
+        match phoneme {
+            "p" => consonant(Stop, 0, false),
+            "b" => consonant(Stop, 0, true),
+            "t" => consonant(Stop, 1, false),
+            "d" => consonant(Stop, 1, true),
+            "k" => consonant(Stop, 4, false),
+            "g" => consonant(Stop, 4, true),
+            "q" => consonant(Stop, 5, false),
+            "ʔ" => consonant(Stop, 7, false),
+            "tʼ" => consonant(Ejective, 1, false),
+            "kʼ" => consonant(Ejective, 4, false),
+            "f" => consonant(Fricative, 0, false),
+            "v" => consonant(Fricative, 0, true),
+            "θ" => consonant(Fricative, 1, false),
+            "ð" => consonant(Fricative, 1, true),
+            "s" => consonant(Fricative, 1, false),
+            "z" => consonant(Fricative, 1, true),
+            "ʃ" => consonant(Fricative, 2, false),
+            "ʒ" => consonant(Fricative, 2, true),
+            "x" => consonant(Fricative, 4, false),
+            "ħ" => consonant(Fricative, 6, false),
+            "ʕ" => consonant(Fricative, 6, true),
+            "h" => consonant(Fricative, 7, false),
+            "m" => consonant(Nasal, 0, true),
+            "n" => consonant(Nasal, 1, true),
+            "ŋ" => consonant(Nasal, 4, true),
+            "l" => consonant(Liquid, 1, true),
+            "r" => consonant(Liquid, 1, true),
+            "w" => consonant(Glide, 0, true),
+            "j" => consonant(Glide, 3, true),
+            "a" => vowel(0, 1, false),
+            "e" => vowel(1, 0, false),
+            "i" => vowel(2, 0, false),
+            "o" => vowel(1, 2, true),
+            "u" => vowel(2, 2, true),
+            "ə" => vowel(1, 1, false),
+            _ => None,
+        }
+    }
+
+    /// A rough articulatory distance between two sets of features.
+    ///
+    /// Consonants and vowels are never close to each other.
+    pub fn distance(&self, other: &Self) -> f32 {
+        match (*self, *other) {
+            (
+                Self::Consonant {
+                    manner: m1,
+                    place: p1,
+                    voiced: v1,
+                },
+                Self::Consonant {
+                    manner: m2,
+                    place: p2,
+                    voiced: v2,
+                },
+            ) => {
+                let manner = match (m1, m2) {
+                    _ if m1 == m2 => 0.0,
+                    (Manner::Stop, Manner::Ejective) | (Manner::Ejective, Manner::Stop) => 1.0,
+                    (Manner::Liquid, Manner::Glide) | (Manner::Glide, Manner::Liquid) => 1.0,
+                    (Manner::Stop, Manner::Fricative) | (Manner::Fricative, Manner::Stop) => 1.5,
+                    _ => 2.5,
+                };
+                let place = p1.abs_diff(p2) as f32 * 0.5;
+                let voicing = if v1 == v2 { 0.0 } else { 0.75 };
+                manner + place + voicing
+            }
+            (
+                Self::Vowel {
+                    height: h1,
+                    backness: b1,
+                    rounded: r1,
+                },
+                Self::Vowel {
+                    height: h2,
+                    backness: b2,
+                    rounded: r2,
+                },
+            ) => {
+                let rounding = if r1 == r2 { 0.0 } else { 0.5 };
+                h1.abs_diff(h2) as f32 + b1.abs_diff(b2) as f32 + rounding
+            }
+            _ => f32::INFINITY,
+        }
+    }
+}
+
 /// Syllable structure patterns (e.g., CV, CVC, CCVC).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SyllableStructure {
     /// Vowel only: "a", "i"
     V,
@@ -131,14 +327,114 @@ impl SyllableStructure {
     }
 }
 
+/// The sound combinations a set of syllable patterns allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Phonotactics {
+    /// Most consonants allowed before a vowel in one syllable
+    pub max_onset: usize,
+    /// Most consonants allowed after a vowel in one syllable
+    pub max_coda: usize,
+    /// Whether syllables may start with a vowel
+    pub allows_onsetless: bool,
+    /// Whether two vowels may follow each other
+    pub allows_vowel_sequences: bool,
+}
+
+impl Phonotactics {
+    /// Derive the phonotactics of a set of syllable patterns.
+    pub fn from_patterns(patterns: &[SyllableStructure]) -> Self {
+        let mut phonotactics = Self {
+            max_onset: 0,
+            max_coda: 0,
+            allows_onsetless: false,
+            allows_vowel_sequences: false,
+        };
+
+        for pattern in patterns {
+            let shape = pattern.pattern();
+            let onset = shape.chars().take_while(|&c| c == 'C').count();
+            let coda = shape.chars().rev().take_while(|&c| c == 'C').count();
+            phonotactics.max_onset = phonotactics.max_onset.max(onset);
+            phonotactics.max_coda = phonotactics.max_coda.max(coda);
+            phonotactics.allows_onsetless |= onset == 0;
+            phonotactics.allows_vowel_sequences |= shape.contains("VV");
+        }
+
+        phonotactics
+    }
+
+    /// Repair a sequence of phonemes so that it fits these phonotactics.
+    ///
+    /// Overlong consonant clusters are broken up with `epenthetic_vowel`, vowel
+    /// sequences are shortened, and a word-initial vowel gets `prothetic_consonant`
+    /// in front of it if syllables need an onset.
+    pub fn repair(
+        &self,
+        segments: &[&str],
+        epenthetic_vowel: &str,
+        prothetic_consonant: &str,
+    ) -> Vec<String> {
+        // Split into alternating runs of consonants and vowels
+        let mut runs: Vec<(bool, Vec<&str>)> = Vec::new();
+        for &segment in segments {
+            let vowel = is_vowel_symbol(segment);
+            match runs.last_mut() {
+                Some((is_vowel, run)) if *is_vowel == vowel => run.push(segment),
+                _ => runs.push((vowel, vec![segment])),
+            }
+        }
+
+        // A word needs at least one vowel to carry its syllables
+        if !runs.iter().any(|(is_vowel, _)| *is_vowel) {
+            runs.push((true, vec![epenthetic_vowel]));
+        }
+
+        let max_vowels = if self.allows_vowel_sequences { 2 } else { 1 };
+        let max_onset = self.max_onset.max(1);
+        let last = runs.len() - 1;
+        let mut repaired = Vec::new();
+
+        for (i, (is_vowel, run)) in runs.iter().enumerate() {
+            if *is_vowel {
+                if i == 0 && !self.allows_onsetless {
+                    repaired.push(prothetic_consonant.to_string());
+                }
+                repaired.extend(run.iter().take(max_vowels).map(|s| s.to_string()));
+                continue;
+            }
+
+            // Consonants kept as a coda of the previous syllable and an onset of the next
+            let coda = if i == 0 { 0 } else { self.max_coda.min(run.len()) };
+            let onset = if i == last {
+                0
+            } else {
+                max_onset.min(run.len() - coda)
+            };
+
+            for (j, consonant) in run.iter().enumerate() {
+                repaired.push(consonant.to_string());
+                let stranded = j >= coda && j < run.len() - onset;
+                if stranded {
+                    repaired.push(epenthetic_vowel.to_string());
+                }
+            }
+        }
+
+        repaired
+    }
+}
+
 /// Prosodic system (stress, tone, intonation).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProsodicSystem {
     /// Stress pattern: None, Initial, Final, Penultimate, etc.
     pub stress_pattern: StressPattern,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StressPattern {
     /// No distinctive stress
     None,
@@ -179,6 +475,35 @@ mod tests {
         assert_eq!(inventory.all_consonants().len(), 9);
     }
 
+    #[test]
+    fn test_feature_distance() {
+        let p = PhonemeFeatures::of("p").unwrap();
+        let b = PhonemeFeatures::of("b").unwrap();
+        let k = PhonemeFeatures::of("k").unwrap();
+        let a = PhonemeFeatures::of("a").unwrap();
+
+        assert!(p.distance(&b) < p.distance(&k));
+        assert!(p.distance(&a).is_infinite());
+        assert!(PhonemeFeatures::of("!").is_none());
+    }
+
+    #[test]
+    fn test_phonotactic_repair() {
+        let cv = Phonotactics::from_patterns(&[SyllableStructure::CV]);
+        assert_eq!(cv.max_onset, 1);
+        assert_eq!(cv.max_coda, 0);
+        assert_eq!(cv.repair(&["s", "t", "a", "r"], "i", "h").concat(), "sitari");
+        assert_eq!(cv.repair(&["a", "i"], "i", "h").concat(), "ha");
+
+        let cvc = Phonotactics::from_patterns(&[
+            SyllableStructure::CV,
+            SyllableStructure::CVC,
+            SyllableStructure::V,
+        ]);
+        assert_eq!(cvc.repair(&["a", "m", "p", "l", "a"], "i", "h").concat(), "ampila");
+        assert_eq!(cvc.repair(&["k", "a", "s", "t"], "i", "h").concat(), "kasti");
+    }
+
     #[test]
     fn test_syllable_structure() {
         assert_eq!(SyllableStructure::CVC.pattern(), "CVC");
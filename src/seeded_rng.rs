@@ -1,44 +1,182 @@
-//! Deterministic random number generator for consistent language generation.
+//! Deterministic random number generation for consistent language generation.
+//!
+//! Generation draws from a [`SeededRng`], which wraps one of several generators.
+//! New languages use xoshiro256**, while languages saved under the first algorithm
+//! version keep the linear congruential generator of the original JavaScript
+//! implementation, so they go on producing the same words.
 
-/// A simple linear congruential generator (LCG) for deterministic randomness.
-/// This ensures that the same seed always produces the same sequence of numbers.
-pub struct SeededRng {
+use crate::error::Error;
+use alloc::boxed::Box;
+
+/// A source of uniformly distributed random numbers.
+///
+/// Implement this to drive a [`SeededRng`] with a generator of your own.
+pub trait RandomSource {
+    /// Generate the next random number in [0, 1).
+    fn next_f64(&mut self) -> f64;
+}
+
+/// The linear congruential generator of the JavaScript implementation.
+///
+/// Its state has only 233280 values, so seeds that are equal modulo 233280 produce
+/// identical streams. It is kept for compatibility with the JavaScript demo and
+/// with languages generated before xoshiro256** became the default.
+#[derive(Debug, Clone)]
+pub struct JsLcg {
     state: u64,
 }
 
-impl SeededRng {
-    /// Create a new seeded RNG with the given seed.
+impl JsLcg {
+    /// Create a new generator with the given seed.
     pub fn new(seed: u64) -> Self {
         Self { state: seed }
     }
+}
 
-    /// Generate the next random number in [0, 1).
-    pub fn next(&mut self) -> f64 {
+impl RandomSource for JsLcg {
+    fn next_f64(&mut self) -> f64 {
         // LCG parameters (same as JavaScript implementation)
         self.state = (self.state.wrapping_mul(9301).wrapping_add(49297)) % 233280;
         self.state as f64 / 233280.0
     }
+}
+
+/// The xoshiro256** generator, seeded through SplitMix64.
+///
+/// Every 64-bit seed gives its own stream, with a period of 2^256 - 1.
+#[derive(Debug, Clone)]
+pub struct Xoshiro256StarStar {
+    s: [u64; 4],
+}
+
+impl Xoshiro256StarStar {
+    /// Create a new generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed;
+        let mut s = [0; 4];
+        for word in &mut s {
+            *word = splitmix64(&mut state);
+        }
+        Self { s }
+    }
+
+    /// Generate the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+
+        result
+    }
+}
+
+impl RandomSource for Xoshiro256StarStar {
+    fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill an f64 mantissa exactly
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+/// One step of SplitMix64, used to expand a seed into generator state.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// The built-in generators a [`SeededRng`] can use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RngKind {
+    /// xoshiro256** (the default)
+    #[default]
+    Xoshiro256StarStar,
+    /// The JavaScript-compatible linear congruential generator
+    JsCompatible,
+}
+
+/// The generator behind a [`SeededRng`].
+enum Source {
+    Js(JsLcg),
+    Xoshiro(Xoshiro256StarStar),
+    Custom(Box<dyn RandomSource + Send>),
+}
+
+/// A deterministic random number generator: the same seed always produces the
+/// same sequence of numbers.
+pub struct SeededRng {
+    source: Source,
+}
+
+impl SeededRng {
+    /// Create a new seeded RNG with the given seed, using the default generator.
+    pub fn new(seed: u64) -> Self {
+        Self::with_kind(RngKind::default(), seed)
+    }
+
+    /// Create a new seeded RNG using one of the built-in generators.
+    pub fn with_kind(kind: RngKind, seed: u64) -> Self {
+        let source = match kind {
+            RngKind::Xoshiro256StarStar => Source::Xoshiro(Xoshiro256StarStar::new(seed)),
+            RngKind::JsCompatible => Source::Js(JsLcg::new(seed)),
+        };
+        Self { source }
+    }
+
+    /// Create a seeded RNG driven by a custom generator.
+    pub fn from_source<R: RandomSource + Send + 'static>(source: R) -> Self {
+        Self {
+            source: Source::Custom(Box::new(source)),
+        }
+    }
+
+    /// Generate the next random number in [0, 1).
+    #[allow(clippy::should_implement_trait)] // Not an iterator: the stream never ends
+    pub fn next(&mut self) -> f64 {
+        match &mut self.source {
+            Source::Js(lcg) => lcg.next_f64(),
+            Source::Xoshiro(xoshiro) => xoshiro.next_f64(),
+            Source::Custom(source) => source.next_f64(),
+        }
+    }
 
     /// Choose a random element from a slice.
-    pub fn choice<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+    ///
+    /// Returns [`Error::EmptyChoice`] if the slice is empty.
+    pub fn choice<'a, T>(&mut self, items: &'a [T]) -> Result<&'a T, Error> {
+        if items.is_empty() {
+            return Err(Error::EmptyChoice);
+        }
         let index = (self.next() * items.len() as f64) as usize;
-        &items[index.min(items.len() - 1)]
+        Ok(&items[index.min(items.len() - 1)])
     }
 
     /// Choose an index based on weighted probabilities.
-    /// Returns the index of the selected item.
-    pub fn weighted_choice(&mut self, weights: &[f32]) -> usize {
+    /// Returns the index of the selected item, or [`Error::EmptyChoice`] if there
+    /// are no weights.
+    pub fn weighted_choice(&mut self, weights: &[f32]) -> Result<usize, Error> {
+        if weights.is_empty() {
+            return Err(Error::EmptyChoice);
+        }
         let total: f32 = weights.iter().sum();
         let mut rand = self.next() as f32 * total;
 
         for (i, &weight) in weights.iter().enumerate() {
             if rand < weight {
-                return i;
+                return Ok(i);
             }
             rand -= weight;
         }
 
-        weights.len() - 1
+        Ok(weights.len() - 1)
     }
 
     /// Generate a random integer in the range [min, max).
@@ -63,9 +201,96 @@ pub fn hash_deterministic(concept: &str, language_seed: u64) -> u64 {
     concept_hash.wrapping_mul(31).wrapping_add(language_seed)
 }
 
+/// Second half of the SipHash key used by [`stable_hash`]: "phyla-ln" in ASCII.
+const STABLE_HASH_KEY: u64 = 0x7068_796c_612d_6c6e;
+
+/// Hash a concept under a language seed with SipHash-2-4.
+///
+/// The concept's UTF-8 bytes are hashed with the 128-bit key made of the seed
+/// and a fixed constant, both read as little-endian words. The result depends
+/// only on those inputs: it is the same on every platform, pointer width and
+/// endianness, and it will never change in a later release, since saved
+/// languages rely on it.
+pub fn stable_hash(concept: &str, language_seed: u64) -> u64 {
+    siphash24(language_seed, STABLE_HASH_KEY, concept.as_bytes())
+}
+
+/// SipHash-2-4 of a message under the key `(k0, k1)`.
+fn siphash24(k0: u64, k1: u64, message: &[u8]) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f_6d65_7073_6575,
+        k1 ^ 0x646f_7261_6e64_6f6d,
+        k0 ^ 0x6c79_6765_6e65_7261,
+        k1 ^ 0x7465_6462_7974_6573,
+    ];
+
+    let mut chunks = message.chunks_exact(8);
+    for chunk in &mut chunks {
+        let m = u64::from_le_bytes(chunk.try_into().unwrap());
+        v[3] ^= m;
+        sip_round(&mut v);
+        sip_round(&mut v);
+        v[0] ^= m;
+    }
+
+    // The last block holds the remaining bytes and the message length
+    let mut last = [0u8; 8];
+    let rest = chunks.remainder();
+    last[..rest.len()].copy_from_slice(rest);
+    let m = u64::from_le_bytes(last) | ((message.len() as u64) << 56);
+    v[3] ^= m;
+    sip_round(&mut v);
+    sip_round(&mut v);
+    v[0] ^= m;
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        sip_round(&mut v);
+    }
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+/// One SipRound.
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+/// The built-in ways of hashing a concept into a generation seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HashKind {
+    /// Keyed SipHash-2-4 (the default), see [`stable_hash`]
+    #[default]
+    SipHash24,
+    /// The 32-bit string hash of the JavaScript implementation
+    ///
+    /// Collides easily: "Aa" and "BB" hash alike, for example.
+    Legacy,
+}
+
+impl HashKind {
+    /// Hash a concept under a language seed.
+    pub fn hash(&self, concept: &str, language_seed: u64) -> u64 {
+        match self {
+            HashKind::SipHash24 => stable_hash(concept, language_seed),
+            HashKind::Legacy => hash_deterministic(concept, language_seed),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_determinism() {
@@ -92,9 +317,9 @@ mod tests {
         let mut rng = SeededRng::new(42);
         let weights = vec![0.5, 0.3, 0.2];
 
-        let mut counts = vec![0; 3];
+        let mut counts = [0; 3];
         for _ in 0..1000 {
-            let choice = rng.weighted_choice(&weights);
+            let choice = rng.weighted_choice(&weights).unwrap();
             counts[choice] += 1;
         }
 
@@ -102,5 +327,124 @@ mod tests {
         assert!(counts[0] > counts[1]);
         assert!(counts[1] > counts[2]);
     }
-}
 
+    #[test]
+    fn test_empty_choices_are_errors() {
+        let mut rng = SeededRng::new(42);
+        let empty: [u8; 0] = [];
+        assert!(matches!(rng.choice(&empty), Err(Error::EmptyChoice)));
+        assert!(matches!(rng.weighted_choice(&[]), Err(Error::EmptyChoice)));
+    }
+
+    #[test]
+    fn test_reference_outputs() {
+        // First SplitMix64 output for seed 0, from the reference implementation
+        assert_eq!(splitmix64(&mut 0), 0xE220_A839_7B1D_CDAF);
+
+        // The JS-compatible generator matches the original formula
+        let mut lcg = JsLcg::new(12345);
+        let expected = (12345u64 * 9301 + 49297) % 233280;
+        assert_eq!(lcg.next_f64(), expected as f64 / 233280.0);
+    }
+
+    #[test]
+    fn test_lcg_seed_collisions_are_gone() {
+        // Seeds equal modulo 233280 collide completely under the LCG...
+        let mut a = SeededRng::with_kind(RngKind::JsCompatible, 7);
+        let mut b = SeededRng::with_kind(RngKind::JsCompatible, 7 + 233280 * 1000);
+        assert!((0..100).all(|_| a.next() == b.next()));
+
+        // ...but not under xoshiro256**
+        let streams: HashSet<u64> = (0..10_000u64)
+            .map(|i| {
+                let mut rng = Xoshiro256StarStar::new(7 + 233280 * i);
+                rng.next_u64()
+            })
+            .collect();
+        assert_eq!(streams.len(), 10_000);
+    }
+
+    #[test]
+    fn test_distinct_seeds_give_distinct_streams() {
+        // The LCG has at most 233280 distinct streams; sample many more seeds
+        let seeds = (0..500_000u64).map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+        let mut lcg_streams = HashSet::new();
+        let mut xoshiro_streams = HashSet::new();
+        for seed in seeds {
+            let mut lcg = JsLcg::new(seed);
+            lcg_streams.insert(lcg.next_f64().to_bits());
+            xoshiro_streams.insert(Xoshiro256StarStar::new(seed).next_u64());
+        }
+
+        assert!(lcg_streams.len() <= 233280);
+        assert_eq!(xoshiro_streams.len(), 500_000);
+    }
+
+    #[test]
+    fn test_uniformity() {
+        // Chi-squared test over 16 buckets; the 99.9% critical value for 15
+        // degrees of freedom is 37.7
+        let mut rng = SeededRng::new(2024);
+        let mut buckets = [0u32; 16];
+        let samples = 160_000;
+        for _ in 0..samples {
+            buckets[(rng.next() * 16.0) as usize] += 1;
+        }
+
+        let expected = samples as f64 / 16.0;
+        let chi_squared: f64 = buckets
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared < 37.7, "chi-squared {}", chi_squared);
+    }
+
+    #[test]
+    fn test_custom_source() {
+        struct Constant;
+        impl RandomSource for Constant {
+            fn next_f64(&mut self) -> f64 {
+                0.5
+            }
+        }
+
+        let mut rng = SeededRng::from_source(Constant);
+        assert_eq!(*rng.choice(&[1, 2, 3]).unwrap(), 2);
+        assert_eq!(rng.range(0, 10), 5);
+    }
+
+    #[test]
+    fn test_siphash_reference_vectors() {
+        // From the SipHash paper, with key 00 01 .. 0f
+        let k0 = u64::from_le_bytes([0, 1, 2, 3, 4, 5, 6, 7]);
+        let k1 = u64::from_le_bytes([8, 9, 10, 11, 12, 13, 14, 15]);
+        assert_eq!(siphash24(k0, k1, &[]), 0x726f_db47_dd0e_0e31);
+        let message: Vec<u8> = (0..15).collect();
+        assert_eq!(siphash24(k0, k1, &message), 0xa129_ca61_49be_45e5);
+    }
+
+    #[test]
+    fn test_stable_hash_is_stable() {
+        // Pinned values: these must never change between releases
+        assert_eq!(stable_hash("water", 12345), 0x0720_e1b1_db5a_fed1);
+        assert_eq!(stable_hash("", 0), 0x0ccb_d682_c45a_3819);
+    }
+
+    #[test]
+    fn test_stable_hash_avoids_legacy_collisions() {
+        assert_eq!(hash_string("Aa"), hash_string("BB"));
+        assert_ne!(stable_hash("Aa", 7), stable_hash("BB", 7));
+
+        // The legacy hash adds the seed, so shifted concepts and seeds collide too
+        let legacy = HashKind::Legacy;
+        assert_eq!(legacy.hash("b", 0), legacy.hash("a", 31));
+
+        let mut seen = HashSet::new();
+        for seed in 0..100 {
+            for i in 0..1000 {
+                assert!(seen.insert(HashKind::SipHash24.hash(&format!("name_{}", i), seed)));
+            }
+        }
+    }
+}
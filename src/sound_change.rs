@@ -0,0 +1,301 @@
+//! Sound changes: regular rewrite rules applied to generated words.
+//!
+//! A language's words are always generated from its original phoneme inventory, and
+//! sound changes are applied afterwards as an ordered list of rules. This keeps the
+//! underlying vocabulary stable while dialects and later stages of a language drift
+//! away from it in a regular, recognizable way.
+
+use crate::culture::Geography;
+use crate::phonology::{is_vowel_symbol, PhonemeInventory};
+use crate::seeded_rng::SeededRng;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// The phonological environment in which a sound change applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Environment {
+    /// The change applies everywhere
+    Anywhere,
+    /// Only at the start of a word
+    WordInitial,
+    /// Only at the end of a word
+    WordFinal,
+    /// Only between two vowels
+    Intervocalic,
+}
+
+/// A single regular sound change, e.g. "p becomes f between vowels".
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoundChange {
+    /// The phoneme affected by the change
+    pub from: String,
+    /// The phoneme it becomes (empty for deletion)
+    pub to: String,
+    /// Where the change applies
+    pub environment: Environment,
+}
+
+impl SoundChange {
+    /// Create a new sound change.
+    pub fn new(from: &str, to: &str, environment: Environment) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            environment,
+        }
+    }
+
+    /// Apply this change to a segmented word in place.
+    fn apply(&self, segments: &mut Vec<String>) {
+        let mut i = 0;
+        while i < segments.len() {
+            if segments[i] == self.from && self.matches_environment(segments, i) {
+                if self.to.is_empty() {
+                    segments.remove(i);
+                    continue;
+                }
+                segments[i] = self.to.clone();
+            }
+            i += 1;
+        }
+    }
+
+    /// Check whether the segment at `index` is in this change's environment.
+    fn matches_environment(&self, segments: &[String], index: usize) -> bool {
+        match self.environment {
+            Environment::Anywhere => true,
+            Environment::WordInitial => index == 0,
+            Environment::WordFinal => index + 1 == segments.len(),
+            Environment::Intervocalic => {
+                index > 0
+                    && index + 1 < segments.len()
+                    && is_vowel_symbol(&segments[index - 1])
+                    && is_vowel_symbol(&segments[index + 1])
+            }
+        }
+    }
+}
+
+/// Apply a sequence of sound changes to a word.
+///
+/// The word is segmented against `inventory`, the `extra` phonemes and every phoneme
+/// introduced by the changes themselves, so multi-character phonemes like "kʼ" are
+/// treated as units.
+pub fn apply_sound_changes(
+    word: &str,
+    changes: &[SoundChange],
+    inventory: &PhonemeInventory,
+    extra: &[String],
+) -> String {
+    if changes.is_empty() {
+        return word.to_string();
+    }
+
+    let extra: Vec<&str> = changes
+        .iter()
+        .flat_map(|c| [c.from.as_str(), c.to.as_str()])
+        .chain(extra.iter().map(String::as_str))
+        .collect();
+    let mut segments: Vec<String> = inventory
+        .segment_with(word, &extra)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+    for change in changes {
+        change.apply(&mut segments);
+    }
+
+    segments.concat()
+}
+
+/// Candidate sound changes that are natural for any language.
+const COMMON_CHANGES: &[(&str, &str, Environment)] = &[
+    // Lenition
+    ("p", "f", Environment::Intervocalic),
+    ("t", "s", Environment::Intervocalic),
+    ("k", "x", Environment::Intervocalic),
+    // Voicing between vowels
+    ("p", "b", Environment::Intervocalic),
+    ("t", "d", Environment::Intervocalic),
+    ("k", "g", Environment::Intervocalic),
+    // Final devoicing
+    ("b", "p", Environment::WordFinal),
+    ("d", "t", Environment::WordFinal),
+    ("g", "k", Environment::WordFinal),
+    ("z", "s", Environment::WordFinal),
+    // Debuccalization and loss
+    ("s", "h", Environment::WordInitial),
+    ("h", "", Environment::Anywhere),
+    ("x", "h", Environment::Anywhere),
+    // Liquids and nasals
+    ("r", "l", Environment::Anywhere),
+    ("l", "r", Environment::Intervocalic),
+    ("n", "ŋ", Environment::WordFinal),
+    ("m", "n", Environment::WordFinal),
+    ("ʃ", "s", Environment::Anywhere),
+    // Vowel shifts
+    ("a", "e", Environment::WordFinal),
+    ("u", "o", Environment::Anywhere),
+    ("i", "e", Environment::WordFinal),
+    ("e", "i", Environment::Anywhere),
+    ("o", "u", Environment::Anywhere),
+];
+
+/// Geography-flavoured candidate sound changes.
+fn regional_changes(geography: Geography) -> &'static [(&'static str, &'static str, Environment)] {
+    match geography {
+        Geography::Mountains => &[
+            ("k", "kʼ", Environment::WordInitial),
+            ("t", "tʼ", Environment::WordInitial),
+            ("b", "p", Environment::Anywhere),
+        ],
+        Geography::Coastal => &[
+            ("k", "h", Environment::Intervocalic),
+            ("t", "r", Environment::Intervocalic),
+            ("s", "h", Environment::Anywhere),
+        ],
+        Geography::Desert => &[
+            ("k", "q", Environment::Anywhere),
+            ("h", "ħ", Environment::WordInitial),
+            ("s", "ʃ", Environment::Anywhere),
+        ],
+        Geography::Forest => &[
+            ("b", "m", Environment::WordInitial),
+            ("d", "n", Environment::WordInitial),
+            ("p", "f", Environment::Anywhere),
+        ],
+        Geography::Plains | Geography::RiverValley => &[
+            ("v", "f", Environment::Anywhere),
+            ("ʒ", "ʃ", Environment::Anywhere),
+            ("g", "", Environment::Intervocalic),
+        ],
+    }
+}
+
+/// Check whether `from` becoming `to` is one of the natural sound changes.
+///
+/// Used to decide the direction of change when reconstructing a proto-language.
+pub(crate) fn is_natural_change(from: &str, to: &str) -> bool {
+    let geographies = [
+        Geography::Mountains,
+        Geography::Coastal,
+        Geography::Desert,
+        Geography::Forest,
+        Geography::Plains,
+    ];
+    COMMON_CHANGES
+        .iter()
+        .chain(geographies.iter().flat_map(|g| regional_changes(*g)))
+        .any(|(f, t, _)| *f == from && *t == to)
+}
+
+/// List the sound changes that could plausibly affect a language, in a
+/// deterministic order chosen by `rng`.
+///
+/// Each candidate only targets a phoneme the language actually has at that point,
+/// taking earlier candidates in the list into account, and never simply undoes an
+/// earlier one, so any prefix of the result is a coherent chain of changes.
+pub(crate) fn candidate_changes(
+    inventory: &PhonemeInventory,
+    geography: Geography,
+    rng: &mut SeededRng,
+) -> Vec<SoundChange> {
+    let regional = regional_changes(geography);
+    let pool: Vec<(&str, &str, Environment)> = regional
+        .iter()
+        .chain(COMMON_CHANGES.iter())
+        .copied()
+        .collect();
+
+    // Deterministic shuffle, keeping regional changes slightly ahead on average
+    let mut order: Vec<(f64, usize)> = (0..pool.len())
+        .map(|i| {
+            let bias = if i < regional.len() { 0.3 } else { 0.0 };
+            (rng.next() - bias, i)
+        })
+        .collect();
+    order.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut present: Vec<String> = inventory
+        .all_consonants()
+        .iter()
+        .map(|c| c.0.clone())
+        .chain(inventory.vowels.iter().map(|v| v.0.clone()))
+        .collect();
+
+    let mut changes = Vec::new();
+    for (_, i) in order {
+        let (from, to, environment) = pool[i];
+        let undoes_earlier = changes
+            .iter()
+            .any(|c: &SoundChange| c.from == to && c.to == from);
+        if undoes_earlier || !present.iter().any(|p| p == from) {
+            continue;
+        }
+        if !to.is_empty() && !present.iter().any(|p| p == to) {
+            present.push(to.to_string());
+        }
+        changes.push(SoundChange::new(from, to, environment));
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::phonology::{Consonant, Vowel};
+
+    fn inventory() -> PhonemeInventory {
+        PhonemeInventory {
+            stops: vec![
+                Consonant::new("p"),
+                Consonant::new("t"),
+                Consonant::new("kʼ"),
+            ],
+            fricatives: vec![Consonant::new("s"), Consonant::new("h")],
+            nasals: vec![Consonant::new("m"), Consonant::new("n")],
+            liquids: vec![Consonant::new("l"), Consonant::new("r")],
+            glides: vec![],
+            vowels: vec![Vowel::new("a"), Vowel::new("i"), Vowel::new("u")],
+            category_weights: vec![0.25, 0.25, 0.25, 0.25, 0.0],
+        }
+    }
+
+    #[test]
+    fn test_environments() {
+        let inv = inventory();
+
+        let lenition = [SoundChange::new("p", "f", Environment::Intervocalic)];
+        assert_eq!(apply_sound_changes("papap", &lenition, &inv, &[]), "pafap");
+
+        let initial = [SoundChange::new("s", "h", Environment::WordInitial)];
+        assert_eq!(apply_sound_changes("sasa", &initial, &inv, &[]), "hasa");
+
+        let final_loss = [SoundChange::new("n", "", Environment::WordFinal)];
+        assert_eq!(apply_sound_changes("nanan", &final_loss, &inv, &[]), "nana");
+    }
+
+    #[test]
+    fn test_multichar_phonemes_are_units() {
+        let inv = inventory();
+        // "kʼ" is a single segment, so the plain "t" next to it is untouched
+        let changes = [SoundChange::new("kʼ", "k", Environment::Anywhere)];
+        assert_eq!(apply_sound_changes("kʼatkʼ", &changes, &inv, &[]), "katk");
+    }
+
+    #[test]
+    fn test_changes_apply_in_order() {
+        let inv = inventory();
+        let changes = [
+            SoundChange::new("p", "f", Environment::Anywhere),
+            SoundChange::new("f", "h", Environment::Anywhere),
+        ];
+        assert_eq!(apply_sound_changes("pipa", &changes, &inv, &[]), "hiha");
+    }
+}
@@ -0,0 +1,247 @@
+//! JavaScript bindings, built with `wasm-bindgen` under the `wasm` feature.
+//!
+//! The bindings are a thin layer over [`Language`]: every method calls the same
+//! Rust code as a native program, so a language generated in the browser speaks
+//! exactly the same words as one generated on a server. Seeds and name IDs are
+//! `u64`, which JavaScript receives as `BigInt` so that no seed loses precision.
+
+use crate::culture::{CulturalProfile, Geography};
+use crate::language::Language;
+use crate::naming::epithet::{Characteristic, EpithetContext};
+use crate::naming::personal::PersonalNameContext;
+use crate::naming::place::{PlaceNameContext, PlaceType};
+use wasm_bindgen::prelude::*;
+
+/// A language, as seen from JavaScript.
+#[wasm_bindgen(js_name = Language)]
+pub struct WasmLanguage {
+    inner: Language,
+}
+
+#[wasm_bindgen(js_class = Language)]
+impl WasmLanguage {
+    /// Create a language from six trait scores (1 to 5), a geography and a seed.
+    ///
+    /// Geographies are `mountains`, `coastal`, `desert`, `forest`, `plains` and
+    /// `river_valley`.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        agreeableness: f32,
+        openness: f32,
+        conscientiousness: f32,
+        extraversion: f32,
+        honesty_humility: f32,
+        emotionality: f32,
+        geography: &str,
+        seed: u64,
+    ) -> Result<WasmLanguage, JsError> {
+        let culture = CulturalProfile::try_new(
+            agreeableness,
+            openness,
+            conscientiousness,
+            extraversion,
+            honesty_humility,
+            emotionality,
+        )?;
+        let geography = parse_geography(geography)
+            .ok_or_else(|| JsError::new(&format!("unknown geography: {}", geography)))?;
+        Ok(Language::try_from_culture(culture, geography, seed)?.into())
+    }
+
+    /// Load a language saved with `toJson` or `Language::to_json`.
+    #[cfg(feature = "serde")]
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<WasmLanguage, JsError> {
+        Ok(Language::from_json(json)?.into())
+    }
+
+    /// Save the language in the versioned language file format.
+    #[cfg(feature = "serde")]
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsError> {
+        Ok(self.inner.to_json()?)
+    }
+
+    /// The language's ID.
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.inner.id.clone()
+    }
+
+    /// The algorithm version the language was generated with.
+    #[wasm_bindgen(getter, js_name = algorithmVersion)]
+    pub fn algorithm_version(&self) -> u32 {
+        self.inner.genome.algorithm_version.number()
+    }
+
+    /// The basic word order, such as `SVO`.
+    #[wasm_bindgen(getter, js_name = wordOrder)]
+    pub fn word_order(&self) -> String {
+        format!("{:?}", self.inner.word_order())
+    }
+
+    /// Translate a concept to a word.
+    #[wasm_bindgen(js_name = translateWord)]
+    pub fn translate_word(&self, concept: &str) -> String {
+        self.inner.translate_word(concept)
+    }
+
+    /// Translate a phrase, applying the language's word order.
+    #[wasm_bindgen(js_name = translatePhrase)]
+    pub fn translate_phrase(&self, phrase: &str) -> String {
+        self.inner.translate_phrase(phrase)
+    }
+
+    /// Generate a name from the language's sounds alone.
+    #[wasm_bindgen(js_name = simpleName)]
+    pub fn simple_name(&self, id: u64) -> String {
+        self.inner.naming.generate_simple_name(id)
+    }
+
+    /// Generate a personal name, naming the parent in patronymic cultures.
+    #[wasm_bindgen(js_name = personalName)]
+    pub fn personal_name(&self, id: u64, parent: Option<String>) -> String {
+        let context = match parent {
+            Some(parent) => PersonalNameContext::with_parent(id, parent),
+            None => PersonalNameContext::simple(id),
+        };
+        self.inner.naming.generate_personal_name(&context)
+    }
+
+    /// Generate a name compounded from `count` meaningful morphemes.
+    #[wasm_bindgen(js_name = compoundName)]
+    pub fn compound_name(&self, id: u64, count: usize) -> String {
+        self.inner.naming.generate_compound_name(id, count)
+    }
+
+    /// Generate a place name.
+    ///
+    /// Place types are `settlement`, `natural`, `landmark` and `region`.
+    #[wasm_bindgen(js_name = placeName)]
+    pub fn place_name(&self, id: u64, place_type: &str) -> Result<String, JsError> {
+        let place_type = parse_place_type(place_type)
+            .ok_or_else(|| JsError::new(&format!("unknown place type: {}", place_type)))?;
+        Ok(self
+            .inner
+            .naming
+            .generate_place_name(&PlaceNameContext::new(id, place_type)))
+    }
+
+    /// Generate an epithet for a characteristic such as `wise` or `brave`.
+    ///
+    /// Returns `undefined` when the culture does not use epithets.
+    pub fn epithet(&self, id: u64, characteristic: &str) -> Result<Option<String>, JsError> {
+        let characteristic = parse_characteristic(characteristic)
+            .ok_or_else(|| JsError::new(&format!("unknown characteristic: {}", characteristic)))?;
+        let context = EpithetContext::new(id).with_characteristic(characteristic);
+        Ok(self.inner.naming.generate_epithet(&context))
+    }
+}
+
+impl WasmLanguage {
+    /// The wrapped language.
+    pub fn language(&self) -> &Language {
+        &self.inner
+    }
+}
+
+impl From<Language> for WasmLanguage {
+    fn from(inner: Language) -> Self {
+        WasmLanguage { inner }
+    }
+}
+
+impl From<WasmLanguage> for Language {
+    fn from(language: WasmLanguage) -> Self {
+        language.inner
+    }
+}
+
+/// Lowercase a name and drop separators, so `river_valley` and `RiverValley` match.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn parse_geography(name: &str) -> Option<Geography> {
+    match normalize(name).as_str() {
+        "mountains" => Some(Geography::Mountains),
+        "coastal" => Some(Geography::Coastal),
+        "desert" => Some(Geography::Desert),
+        "forest" => Some(Geography::Forest),
+        "plains" => Some(Geography::Plains),
+        "rivervalley" => Some(Geography::RiverValley),
+        _ => None,
+    }
+}
+
+fn parse_place_type(name: &str) -> Option<PlaceType> {
+    match normalize(name).as_str() {
+        "settlement" => Some(PlaceType::Settlement),
+        "natural" => Some(PlaceType::Natural),
+        "landmark" => Some(PlaceType::Landmark),
+        "region" => Some(PlaceType::Region),
+        _ => None,
+    }
+}
+
+fn parse_characteristic(name: &str) -> Option<Characteristic> {
+    let characteristic = match normalize(name).as_str() {
+        "tall" => Characteristic::Tall,
+        "short" => Characteristic::Short,
+        "strong" => Characteristic::Strong,
+        "swift" => Characteristic::Swift,
+        "wise" => Characteristic::Wise,
+        "cunning" => Characteristic::Cunning,
+        "mad" => Characteristic::Mad,
+        "honest" => Characteristic::Honest,
+        "brave" => Characteristic::Brave,
+        "cruel" => Characteristic::Cruel,
+        "just" => Characteristic::Just,
+        "silent" => Characteristic::Silent,
+        "loud" => Characteristic::Loud,
+        "beloved" => Characteristic::Beloved,
+        "feared" => Characteristic::Feared,
+        _ => return None,
+    };
+    Some(characteristic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_geography() {
+        assert_eq!(parse_geography("coastal"), Some(Geography::Coastal));
+        assert_eq!(
+            parse_geography("river_valley"),
+            Some(Geography::RiverValley)
+        );
+        assert_eq!(parse_geography("RiverValley"), Some(Geography::RiverValley));
+        assert_eq!(parse_geography("ocean"), None);
+    }
+
+    #[test]
+    fn test_parse_names() {
+        assert_eq!(parse_place_type("Settlement"), Some(PlaceType::Settlement));
+        assert_eq!(parse_place_type("city"), None);
+        assert_eq!(parse_characteristic("wise"), Some(Characteristic::Wise));
+        assert_eq!(parse_characteristic("kind"), None);
+    }
+
+    #[test]
+    fn test_bindings_match_language() {
+        let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+        let native = Language::from_culture(culture, Geography::Coastal, 12345);
+        let wasm = WasmLanguage::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0, "coastal", 12345).unwrap();
+
+        assert_eq!(wasm.id(), native.id);
+        assert_eq!(wasm.translate_word("water"), native.translate_word("water"));
+        assert_eq!(wasm.simple_name(7), native.naming.generate_simple_name(7));
+        assert_eq!(wasm.word_order(), format!("{:?}", native.word_order()));
+    }
+}
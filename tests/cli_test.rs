@@ -0,0 +1,146 @@
+//! Tests for the `phyla` command-line tool.
+
+#![cfg(feature = "cli")]
+
+use phyla_lang::Language;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn phyla(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_phyla"))
+        .args(args)
+        .output()
+        .expect("failed to run phyla")
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// Create a language file in a fresh temporary directory.
+fn new_language(name: &str, extra: &[&str]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("phyla_cli_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("language.json");
+    let mut args = vec!["new", path.to_str().unwrap()];
+    args.extend_from_slice(extra);
+    stdout(&phyla(&args));
+    path
+}
+
+#[test]
+fn test_new_writes_a_language_file() {
+    let path = new_language(
+        "new",
+        &["--preset", "coastal", "--seed", "42", "--openness", "4.5"],
+    );
+    let language = Language::load(std::fs::File::open(&path).unwrap()).unwrap();
+
+    assert_eq!(language.id, "lang_42");
+    assert_eq!(language.culture().openness, 4.5);
+    assert_eq!(language.culture().agreeableness, 4.0);
+}
+
+#[test]
+fn test_translate_matches_library() {
+    let path = new_language("translate", &["--seed", "7"]);
+    let language = Language::load(std::fs::File::open(&path).unwrap()).unwrap();
+    let file = path.to_str().unwrap();
+
+    let output = stdout(&phyla(&[
+        "-f",
+        "json",
+        "translate",
+        file,
+        "house",
+        "I see the river",
+    ]));
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json[0]["translation"], language.translate_word("house"));
+    assert_eq!(
+        json[1]["translation"],
+        language.translate_phrase("I see the river")
+    );
+}
+
+#[test]
+fn test_names_dict_and_info() {
+    let path = new_language("names", &["--preset", "mountain", "--seed", "3"]);
+    let language = Language::load(std::fs::File::open(&path).unwrap()).unwrap();
+    let file = path.to_str().unwrap();
+
+    let output = stdout(&phyla(&["names", file, "--kind", "compound", "-n", "3"]));
+    let expected: Vec<String> = (0..3)
+        .map(|id| language.naming.generate_compound_name(id, 2))
+        .collect();
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+
+    let output = stdout(&phyla(&[
+        "-f",
+        "json",
+        "dict",
+        file,
+        "--concepts",
+        "water,fire",
+    ]));
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json[1]["concept"], "fire");
+    assert_eq!(json[1]["word"], language.translate_word("fire"));
+
+    let output = stdout(&phyla(&["info", file]));
+    assert!(output.contains("Word order:"));
+    assert!(output.contains(&format!("{:?}", language.word_order())));
+}
+
+#[test]
+fn test_errors_exit_with_failure() {
+    let output = phyla(&["new", "unused.json", "--openness", "7"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("openness"));
+
+    let output = phyla(&["info", "does/not/exist.json"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_failed_writes_exit_with_failure() {
+    // Every write to /dev/full fails with "no space left on device"
+    let output = phyla(&["new", "/dev/full"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No space left"));
+}
+
+#[test]
+fn test_repl_tunes_and_saves() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let path = new_language("repl", &["--seed", "11"]);
+    let tuned = path.with_file_name("tuned.json");
+    let script = format!("set openness 4.5\nsave {}\nquit\n", tuned.display());
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_phyla"))
+        .args(["repl", path.to_str().unwrap()])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(script.as_bytes())
+        .unwrap();
+    let output = stdout(&child.wait_with_output().unwrap());
+
+    let language = Language::load(std::fs::File::open(&tuned).unwrap()).unwrap();
+    assert_eq!(language.culture().openness, 4.5);
+    assert!(output.contains(&format!("-> {}", language.translate_word("water"))));
+}
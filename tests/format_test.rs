@@ -0,0 +1,89 @@
+//! Tests for the versioned language file format.
+//!
+//! `fixtures/language_v1.json` was saved with algorithm version 1. Whatever the
+//! library's latest algorithms are, loading it must keep producing the words and
+//! names it produced when it was saved.
+
+#![cfg(feature = "serde")]
+
+use phyla_lang::{
+    AlgorithmVersion, CulturalProfile, FormatError, Geography, GrammaticalFunction, Language,
+    PersonalNameContext,
+};
+
+const V1_FIXTURE: &str = include_str!("fixtures/language_v1.json");
+
+#[test]
+fn test_v1_file_keeps_v1_words() {
+    let language = Language::from_json(V1_FIXTURE).unwrap();
+    assert_eq!(language.genome.algorithm_version, AlgorithmVersion::V1);
+    assert_eq!(language.id, "lang_12345_harbor_1200y");
+
+    let expected = [
+        ("water", "imu"),
+        ("fire", "adae"),
+        ("house", "puiulue"),
+        ("market", "ruka"),
+        ("coin", "ninpakum"),
+        ("mother", "mufilluu"),
+    ];
+    for (concept, word) in expected {
+        assert_eq!(language.translate_word(concept), word, "{}", concept);
+    }
+    assert_eq!(
+        language.inflect("house", GrammaticalFunction::Plural),
+        "niiliv puiulue"
+    );
+}
+
+#[test]
+fn test_v1_file_keeps_v1_names() {
+    let language = Language::from_json(V1_FIXTURE).unwrap();
+    let naming = &language.naming;
+
+    let simple: Vec<String> = (0..3).map(|i| naming.generate_simple_name(i)).collect();
+    assert_eq!(simple, ["Alnulliu", "Iumau", "Faullu"]);
+
+    let personal: Vec<String> = (0..3)
+        .map(|i| naming.generate_personal_name(&PersonalNameContext::simple(i)))
+        .collect();
+    assert_eq!(personal, ["Alnulliu", "Iumau", "Faullu"]);
+
+    let compound: Vec<String> = (0..3)
+        .map(|i| naming.generate_compound_name(i, 2))
+        .collect();
+    assert_eq!(compound, ["Tueadae", "Liuulue", "Uklumaule"]);
+}
+
+#[test]
+fn test_save_and_load() {
+    let culture = CulturalProfile::new(2.0, 4.0, 3.0, 3.0, 4.0, 2.0);
+    let language = Language::from_culture(culture, Geography::Plains, 2718);
+
+    let mut file = Vec::new();
+    language.save(&mut file).unwrap();
+    let loaded = Language::load(file.as_slice()).unwrap();
+
+    assert_eq!(loaded.genome.algorithm_version, AlgorithmVersion::LATEST);
+    assert_eq!(
+        loaded.translate_word("river"),
+        language.translate_word("river")
+    );
+    assert_eq!(
+        loaded.naming.generate_simple_name(5),
+        language.naming.generate_simple_name(5)
+    );
+}
+
+#[test]
+fn test_future_version_is_a_migration_error() {
+    let future = V1_FIXTURE.replacen("\"algorithm_version\": 1", "\"algorithm_version\": 9", 1);
+    match Language::from_json(&future) {
+        Err(FormatError::UnsupportedAlgorithmVersion { found, latest }) => {
+            assert_eq!(found, 9);
+            assert_eq!(latest, AlgorithmVersion::LATEST.number());
+        }
+        Err(other) => panic!("unexpected error: {}", other),
+        Ok(_) => panic!("loaded a language with an unknown algorithm version"),
+    }
+}
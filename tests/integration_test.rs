@@ -1,6 +1,8 @@
 //! Integration tests for the phyla-lang library.
 
-use phyla_lang::{CulturalProfile, Geography, Language};
+use phyla_lang::{
+    AlgorithmVersion, CacheConfig, CulturalProfile, Geography, HashKind, Language, CORE_VOCABULARY,
+};
 
 #[test]
 fn test_basic_translation() {
@@ -146,7 +148,86 @@ fn test_cache_functionality() {
     assert_eq!(language.cache_size(), 0);
 }
 
+#[test]
+fn test_bounded_cache_under_threads() {
+    let culture = CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0);
+    let uncached = Language::builder(culture, Geography::Coastal, 12345)
+        .with_cache(CacheConfig::disabled())
+        .build()
+        .unwrap();
+    let expected: Vec<String> = CORE_VOCABULARY
+        .iter()
+        .map(|c| uncached.translate_word(c))
+        .collect();
+
+    // Far fewer slots than concepts, so threads evict each other's words
+    let language = Language::builder(culture, Geography::Coastal, 12345)
+        .with_cache(CacheConfig::new(16).with_shards(4))
+        .build()
+        .unwrap();
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..3 {
+                    let words: Vec<String> = CORE_VOCABULARY
+                        .iter()
+                        .map(|c| language.translate_word(c))
+                        .collect();
+                    assert_eq!(words, expected);
+                }
+            });
+        }
+    });
+
+    let stats = language.cache_stats();
+    assert!(language.cache_size() <= 16);
+    assert_eq!(stats.hits + stats.misses, 4 * 3 * CORE_VOCABULARY.len() as u64);
+    assert!(stats.evictions > 0);
+}
 
+#[test]
+fn test_seeds_no_longer_collide() {
+    let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 3.0, 3.0);
+    let concepts = ["water", "fire", "house", "mountain", "river", "king"];
+    let words = |language: &Language| -> Vec<String> {
+        concepts.iter().map(|c| language.translate_word(c)).collect()
+    };
+
+    for seed in [1u64, 500, 99_999] {
+        let colliding = seed + 233_280 * 17;
+
+        // The JS-compatible generator cannot tell these seeds apart
+        let v1 = |s| {
+            Language::from_culture_with_version(culture, Geography::Plains, s, AlgorithmVersion::V1)
+        };
+        assert_eq!(words(&v1(seed)), words(&v1(colliding)));
+
+        let latest = |s| Language::from_culture(culture, Geography::Plains, s);
+        assert_ne!(words(&latest(seed)), words(&latest(colliding)));
+    }
+}
 
+#[test]
+fn test_concepts_no_longer_collide() {
+    let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 3.0, 3.0);
 
+    // "Aa" and "BB" share a legacy hash, so versions 1 and 2 give them one seed
+    for version in [AlgorithmVersion::V1, AlgorithmVersion::V2] {
+        let language =
+            Language::from_culture_with_version(culture, Geography::Plains, 42, version);
+        assert_eq!(
+            language.genome.concept_seed("Aa"),
+            language.genome.concept_seed("BB")
+        );
+    }
 
+    let language = Language::from_culture(culture, Geography::Plains, 42);
+    assert_eq!(
+        language.genome.algorithm_version.hash_kind(),
+        HashKind::SipHash24
+    );
+    assert_ne!(
+        language.genome.concept_seed("Aa"),
+        language.genome.concept_seed("BB")
+    );
+}
@@ -0,0 +1,178 @@
+//! Round-trip tests for the `serde` feature.
+
+#![cfg(feature = "serde")]
+
+use phyla_lang::{
+    AlgorithmVersion, BirthOrderNaming, Characteristic, CulturalProfile, DialectPoint,
+    EpithetContext, Geography, GrammaticalFunction, Language, MorphemeDatabase, NamingSystem,
+    PersonalNameContext, PlaceNameContext, PlaceType, SemanticField, SurnameInheritance,
+    CORE_VOCABULARY,
+};
+
+fn cultures() -> Vec<(CulturalProfile, Geography, u64)> {
+    vec![
+        (
+            CulturalProfile::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0),
+            Geography::Coastal,
+            12345,
+        ),
+        (
+            CulturalProfile::new(1.5, 2.0, 4.5, 2.0, 3.5, 2.0),
+            Geography::Mountains,
+            67890,
+        ),
+        (
+            CulturalProfile::new(3.0, 5.0, 2.0, 4.0, 3.0, 3.0),
+            Geography::Forest,
+            42,
+        ),
+        (
+            CulturalProfile::new(2.0, 3.0, 3.0, 5.0, 1.5, 3.0),
+            Geography::Desert,
+            7,
+        ),
+        (
+            CulturalProfile::new(3.0, 2.0, 4.0, 3.0, 3.0, 3.0),
+            Geography::RiverValley,
+            99,
+        ),
+    ]
+}
+
+fn round_trip(language: &Language) -> Language {
+    let json = serde_json::to_string(language).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+fn names(naming: &NamingSystem) -> Vec<String> {
+    let mut names = Vec::new();
+    for id in 0..20 {
+        names.push(naming.generate_simple_name(id));
+        names.push(naming.generate_compound_name(id, 2));
+        names.push(
+            naming
+                .generate_personal_name(&PersonalNameContext::with_parent(id, "Aran".to_string())),
+        );
+        names.push(naming.generate_place_name(
+            &PlaceNameContext::new(id, PlaceType::Settlement).with_founder("Aran".to_string()),
+        ));
+        let epithet = EpithetContext::new(id).with_characteristic(Characteristic::Wise);
+        names.extend(naming.generate_epithet(&epithet));
+    }
+    names
+}
+
+fn assert_same_language(original: &Language, loaded: &Language) {
+    assert_eq!(original.id, loaded.id);
+    for concept in CORE_VOCABULARY {
+        assert_eq!(
+            original.translate_word(concept),
+            loaded.translate_word(concept)
+        );
+        assert_eq!(original.etymology(concept), loaded.etymology(concept));
+    }
+    assert_eq!(
+        original.translate_phrase("I bring the beer quickly"),
+        loaded.translate_phrase("I bring the beer quickly")
+    );
+    assert_eq!(names(&original.naming), names(&loaded.naming));
+}
+
+#[test]
+fn test_language_round_trip() {
+    for (culture, geography, seed) in cultures() {
+        let language = Language::from_culture(culture, geography, seed);
+        let loaded = round_trip(&language);
+
+        assert_same_language(&language, &loaded);
+        assert_eq!(loaded.culture().openness, culture.openness);
+        assert_eq!(loaded.geography(), geography);
+    }
+}
+
+#[test]
+fn test_serialization_is_stable() {
+    let (culture, geography, seed) = cultures()[2];
+    let language = Language::from_culture(culture, geography, seed);
+
+    let json = serde_json::to_string(&language).unwrap();
+    let again = serde_json::to_string(&round_trip(&language)).unwrap();
+    assert_eq!(json, again);
+}
+
+#[test]
+fn test_derived_language_round_trip() {
+    let (culture, geography, seed) = cultures()[0];
+    let (donor_culture, donor_geography, donor_seed) = cultures()[3];
+    let donor = Language::from_culture(donor_culture, donor_geography, donor_seed);
+
+    let mut language = Language::from_culture(culture, geography, seed)
+        .dialect(&DialectPoint::new("harbor", Geography::Coastal, 2.0))
+        .evolve(1500, 4);
+    language.borrow_semantic_field(&donor, SemanticField::Trade, 0.9);
+    language.borrow_phonemes(&donor, 0.5);
+
+    let loaded = round_trip(&language);
+    assert_same_language(&language, &loaded);
+    for function in GrammaticalFunction::all() {
+        assert_eq!(
+            language.inflect("house", *function),
+            loaded.inflect("house", *function)
+        );
+    }
+}
+
+#[test]
+fn test_naming_round_trip_keeps_customizations() {
+    let (culture, geography, seed) = cultures()[1];
+    let mut language = Language::from_culture(culture, geography, seed);
+    language.naming.syllables_per_name = 4;
+
+    let loaded = round_trip(&language);
+    assert_eq!(loaded.naming.syllables_per_name, 4);
+    assert_same_language(&language, &loaded);
+
+    // The naming system and morpheme database also serialize on their own
+    let json = serde_json::to_string(&language.naming).unwrap();
+    let naming: NamingSystem = serde_json::from_str(&json).unwrap();
+    assert_eq!(names(&language.naming), names(&naming));
+
+    let json = serde_json::to_string(&language.naming.morphemes).unwrap();
+    let morphemes: MorphemeDatabase = serde_json::from_str(&json).unwrap();
+    assert_eq!(json, serde_json::to_string(&morphemes).unwrap());
+}
+
+#[test]
+fn test_missing_family_customs_come_from_the_culture() {
+    // Low honesty-humility: clan houses and heirs
+    let culture = CulturalProfile::new(3.0, 3.0, 3.0, 3.0, 1.5, 3.0);
+    let customs = [
+        "surname_inheritance",
+        "gender_marking",
+        "birth_order_naming",
+    ];
+
+    for version in [AlgorithmVersion::V4, AlgorithmVersion::LATEST] {
+        let language = Language::from_culture_with_version(culture, Geography::Plains, 7, version);
+        let mut naming = serde_json::to_value(&language.naming).unwrap();
+        let mut saved = serde_json::to_value(&language).unwrap();
+        for custom in customs {
+            naming.as_object_mut().unwrap().remove(custom);
+            saved["naming"].as_object_mut().unwrap().remove(custom);
+        }
+
+        let naming: NamingSystem = serde_json::from_value(naming).unwrap();
+        let loaded: Language = serde_json::from_value(saved).unwrap();
+        for restored in [&naming, &loaded.naming] {
+            assert_eq!(restored.surname_inheritance, SurnameInheritance::Clan);
+            assert_eq!(restored.gender_marking, language.naming.gender_marking);
+            assert_eq!(
+                restored.birth_order_naming,
+                language.naming.birth_order_naming
+            );
+        }
+    }
+
+    let latest = Language::from_culture(culture, Geography::Plains, 7);
+    assert_eq!(latest.naming.birth_order_naming, BirthOrderNaming::Heir);
+}
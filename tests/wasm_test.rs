@@ -0,0 +1,119 @@
+//! Tests that the JavaScript bindings produce the same text on every target.
+//!
+//! `fixtures/wasm_golden.json` was written by a native build. These tests run
+//! natively and, through `wasm-bindgen-test`, in a wasm32 build; both must
+//! reproduce the fixture exactly, so a language made in the browser matches one
+//! made on a server:
+//!
+//! ```text
+//! cargo test --features wasm --test wasm_test
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test --features wasm --test wasm_test --target wasm32-unknown-unknown
+//! ```
+//!
+//! The wasm32 run needs `wasm-bindgen-test-runner` from the `wasm-bindgen-cli`
+//! of the same version as `wasm-bindgen`; the `wasm` job in
+//! `.github/workflows/ci.yml` installs it and runs them.
+//!
+//! If generation changes on purpose, rewrite the fixture from a native build
+//! with `PHYLA_BLESS=1 cargo test --features wasm --test wasm_test`.
+
+#![cfg(feature = "wasm")]
+
+use phyla_lang::WasmLanguage;
+use serde_json::{json, Value};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const GOLDEN: &str = include_str!("fixtures/wasm_golden.json");
+
+/// Trait scores, geography and seed of each language in the fixture.
+const CASES: &[([f32; 6], &str, u64)] = &[
+    ([4.0, 3.0, 2.0, 3.0, 3.0, 4.0], "coastal", 12345),
+    ([1.5, 2.0, 4.5, 2.0, 3.5, 2.0], "mountains", 67890),
+    ([3.0, 5.0, 2.0, 4.0, 1.5, 3.0], "desert", 42),
+    ([4.5, 3.5, 2.5, 2.0, 3.5, 4.0], "river_valley", 0),
+    // Seeds past 2^53 would lose precision as JavaScript numbers
+    ([2.5, 4.0, 3.5, 3.0, 2.0, 1.5], "forest", u64::MAX - 12345),
+];
+
+const WORDS: &[&str] = &["water", "fire", "house", "mother", "river", "market"];
+const PHRASES: &[&str] = &["I bring the beer quickly", "the mother sees the river"];
+const PLACE_TYPES: &[&str] = &["settlement", "natural", "landmark", "region"];
+const CHARACTERISTICS: &[&str] = &["wise", "brave", "swift", "feared"];
+
+/// Everything the bindings say about one language.
+fn outputs(traits: [f32; 6], geography: &str, seed: u64) -> Value {
+    let [a, o, c, e, h, em] = traits;
+    let language = WasmLanguage::new(a, o, c, e, h, em, geography, seed).unwrap();
+    let ids = 0..4u64;
+
+    json!({
+        "id": language.id(),
+        "word_order": language.word_order(),
+        "words": WORDS.iter().map(|w| language.translate_word(w)).collect::<Vec<_>>(),
+        "phrases": PHRASES.iter().map(|p| language.translate_phrase(p)).collect::<Vec<_>>(),
+        "simple_names": ids.clone().map(|id| language.simple_name(id)).collect::<Vec<_>>(),
+        "personal_names": ids
+            .clone()
+            .map(|id| language.personal_name(id, Some("Aru".to_string())))
+            .collect::<Vec<_>>(),
+        "compound_names": ids.clone().map(|id| language.compound_name(id, 2)).collect::<Vec<_>>(),
+        "place_names": PLACE_TYPES
+            .iter()
+            .zip(ids.clone())
+            .map(|(place_type, id)| language.place_name(id, place_type).unwrap())
+            .collect::<Vec<_>>(),
+        "epithets": CHARACTERISTICS
+            .iter()
+            .zip(ids)
+            .map(|(characteristic, id)| language.epithet(id, characteristic).unwrap())
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn all_outputs() -> Value {
+    CASES
+        .iter()
+        .map(|&(traits, geography, seed)| outputs(traits, geography, seed))
+        .collect()
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_bindings_match_golden_fixture() {
+    let actual = all_outputs();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if std::env::var_os("PHYLA_BLESS").is_some() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/wasm_golden.json"
+        );
+        let json = serde_json::to_string_pretty(&actual).unwrap();
+        std::fs::write(path, json + "\n").unwrap();
+        return;
+    }
+
+    let golden: Value = serde_json::from_str(GOLDEN).unwrap();
+    assert_eq!(actual, golden);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_bindings_are_deterministic() {
+    assert_eq!(all_outputs(), all_outputs());
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_json_round_trip_keeps_words() {
+    let language = WasmLanguage::new(4.0, 3.0, 2.0, 3.0, 3.0, 4.0, "coastal", 12345).unwrap();
+    let loaded = WasmLanguage::from_json(&language.to_json().unwrap()).unwrap();
+
+    assert_eq!(loaded.id(), language.id());
+    for word in WORDS {
+        assert_eq!(loaded.translate_word(word), language.translate_word(word));
+    }
+}